@@ -2,7 +2,7 @@ fn main() {
     let mut voro = cc::Build::new();
     voro.cpp(true);
     voro.warnings(false);
-    voro.file("voro/src/voro++.cc");
+    voro.file("src/voro.cc");
     #[cfg(not(target_os = "windows"))]
     voro.flag("-ansi");
     #[cfg(not(target_os = "windows"))]
@@ -10,9 +10,11 @@ fn main() {
     voro.compile("voro++");
 
     let mut bridge = cxx_build::bridges([
+        "src/batch.rs",
         "src/cell.rs",
         "src/container.rs",
         "src/container_loop.rs",
+        "src/draw.rs",
         "src/particle_marker.rs",
         "src/pre_container.rs",
         "src/wall.rs",
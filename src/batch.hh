@@ -0,0 +1,219 @@
+// Computes the Voronoi cells of many particles in a single call, writing
+// the selected properties straight into Rust vectors. See src/batch.rs.
+
+#include <new>
+#include <vector>
+#include "rust/cxx.h"
+
+namespace voro
+{
+    inline void cell_neighbors(voronoicell &, std::vector<int> &v)
+    {
+        v.clear();
+    }
+    inline void cell_neighbors(voronoicell_neighbor &c,
+                               std::vector<int> &v)
+    {
+        c.neighbors(v);
+    }
+
+    // The output vectors of a batch, together with the properties that
+    // are selected.
+    class batch_output
+    {
+    public:
+        batch_output(bool volumes_, bool neighbors_, bool face_areas_,
+                     rust::Vec<int> &ids_, rust::Vec<double> &positions_,
+                     rust::Vec<double> &radii_,
+                     rust::Vec<double> &volumes_out_,
+                     rust::Vec<size_t> &offsets_,
+                     rust::Vec<int> &neighbors_out_,
+                     rust::Vec<double> &face_areas_out_)
+            : volumes(volumes_), neighbors(neighbors_),
+              face_areas(face_areas_), ids(ids_), positions(positions_),
+              radii(radii_), volumes_out(volumes_out_),
+              offsets(offsets_), neighbors_out(neighbors_out_),
+              face_areas_out(face_areas_out_)
+        {
+            if (faces() && offsets.empty())
+                offsets.push_back(0);
+        }
+        bool faces() const { return neighbors || face_areas; }
+        void reserve(size_t n)
+        {
+            ids.reserve(ids.size() + n);
+            positions.reserve(positions.size() + 3 * n);
+            radii.reserve(radii.size() + n);
+            if (volumes)
+                volumes_out.reserve(volumes_out.size() + n);
+            if (faces())
+                offsets.reserve(offsets.size() + n);
+        }
+        template <class v_cell>
+        void record(v_cell &c, int pid, double x, double y, double z,
+                    double r)
+        {
+            ids.push_back(pid);
+            positions.push_back(x);
+            positions.push_back(y);
+            positions.push_back(z);
+            radii.push_back(r);
+            if (volumes)
+                volumes_out.push_back(c.volume());
+            if (neighbors)
+            {
+                cell_neighbors(c, nv);
+                for (size_t i = 0; i < nv.size(); i++)
+                    neighbors_out.push_back(nv[i]);
+            }
+            if (face_areas)
+            {
+                c.face_areas(av);
+                for (size_t i = 0; i < av.size(); i++)
+                    face_areas_out.push_back(av[i]);
+            }
+            if (faces())
+            {
+                size_t count = neighbors ? nv.size() : av.size();
+                offsets.push_back(offsets[offsets.size() - 1] + count);
+            }
+        }
+
+    private:
+        const bool volumes, neighbors, face_areas;
+        rust::Vec<int> &ids;
+        rust::Vec<double> &positions;
+        rust::Vec<double> &radii;
+        rust::Vec<double> &volumes_out;
+        rust::Vec<size_t> &offsets;
+        rust::Vec<int> &neighbors_out;
+        rust::Vec<double> &face_areas_out;
+        std::vector<int> nv;
+        std::vector<double> av;
+    };
+
+    template <class v_cell, class c_class>
+    void compute_all_base(c_class &con, batch_output &out)
+    {
+        out.reserve(con.total_particles());
+        v_cell c;
+        c_loop_all vl(con);
+        if (vl.start())
+            do
+                if (con.compute_cell(c, vl))
+                {
+                    int pid;
+                    double x, y, z, r;
+                    vl.pos(pid, x, y, z, r);
+                    out.record(c, pid, x, y, z, r);
+                }
+            while (vl.inc());
+    }
+
+    // Neighbor information is only tracked when it is requested, since
+    // voronoicell is cheaper to compute than voronoicell_neighbor.
+    template <class c_class>
+    void compute_all(c_class &con, bool volumes, bool neighbors,
+                     bool face_areas, rust::Vec<int> &ids,
+                     rust::Vec<double> &positions,
+                     rust::Vec<double> &radii,
+                     rust::Vec<double> &out_volumes,
+                     rust::Vec<size_t> &offsets,
+                     rust::Vec<int> &out_neighbors,
+                     rust::Vec<double> &out_face_areas)
+    {
+        batch_output out(volumes, neighbors, face_areas, ids, positions,
+                         radii, out_volumes, offsets, out_neighbors,
+                         out_face_areas);
+        if (neighbors)
+            compute_all_base<voronoicell_neighbor>(con, out);
+        else
+            compute_all_base<voronoicell>(con, out);
+    }
+
+    template <class c_class>
+    inline int container_blocks(const c_class &con)
+    {
+        return con.nxyz;
+    }
+
+    inline double particle_radius(const container &, const double *)
+    {
+        return default_radius;
+    }
+    inline double particle_radius(const container_poly &,
+                                  const double *pp)
+    {
+        return pp[3];
+    }
+
+    // A compute engine with its own search state and cells, so that
+    // several engines can compute the cells of the same container
+    // concurrently. Each engine must only be used by one thread.
+    //
+    // container_poly keeps the radius of the current particle in its
+    // radius_poly base, so each engine computes with its own shallow copy
+    // of the container. The copy shares the particles and walls of the
+    // container and is never destroyed, since it owns none of them.
+    template <class c_class>
+    class compute_engine_base
+    {
+    public:
+        compute_engine_base(const c_class &con_)
+            : con(*new (storage) c_class(con_)),
+              vc(con, con.xperiodic ? 2 * con.nx + 1 : con.nx,
+                 con.yperiodic ? 2 * con.ny + 1 : con.ny,
+                 con.zperiodic ? 2 * con.nz + 1 : con.nz) {}
+        void compute_block(int ijk, bool volumes, bool neighbors,
+                           bool face_areas, rust::Vec<int> &ids,
+                           rust::Vec<double> &positions,
+                           rust::Vec<double> &radii,
+                           rust::Vec<double> &out_volumes,
+                           rust::Vec<size_t> &offsets,
+                           rust::Vec<int> &out_neighbors,
+                           rust::Vec<double> &out_face_areas)
+        {
+            batch_output out(volumes, neighbors, face_areas, ids,
+                             positions, radii, out_volumes, offsets,
+                             out_neighbors, out_face_areas);
+            if (neighbors)
+                compute_block_base(cn, ijk, out);
+            else
+                compute_block_base(c, ijk, out);
+        }
+
+    private:
+        template <class v_cell>
+        void compute_block_base(v_cell &vc_cell, int ijk,
+                                batch_output &out)
+        {
+            int ci = ijk % con.nx, cj = (ijk / con.nx) % con.ny,
+                ck = ijk / con.nxy;
+            out.reserve(con.co[ijk]);
+            for (int q = 0; q < con.co[ijk]; q++)
+                if (vc.compute_cell(vc_cell, ijk, q, ci, cj, ck))
+                {
+                    double *pp = con.p[ijk] + con.ps * q;
+                    out.record(vc_cell, con.id[ijk][q], pp[0], pp[1],
+                               pp[2], particle_radius(con, pp));
+                }
+        }
+
+        alignas(c_class) unsigned char storage[sizeof(c_class)];
+        c_class &con;
+        voro_compute<c_class> vc;
+        voronoicell c;
+        voronoicell_neighbor cn;
+    };
+
+    typedef compute_engine_base<container> compute_engine;
+    typedef compute_engine_base<container_poly> compute_engine_poly;
+
+    template <class c_class>
+    inline std::unique_ptr<compute_engine_base<c_class>>
+    new_compute_engine(const c_class &con)
+    {
+        return std::unique_ptr<compute_engine_base<c_class>>(
+            new compute_engine_base<c_class>(con));
+    }
+}
@@ -0,0 +1,751 @@
+//! Batch computation of Voronoi cells.
+
+#[cxx::bridge(namespace = "voro")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("voro_rs/src/boilerplate.hh");
+
+        type container = crate::container::ffi::container;
+        type container_poly =
+            crate::container::ffi::container_poly;
+
+        #[rust_name = "compute_all_0"]
+        fn compute_all(
+            con: Pin<&mut container>,
+            volumes: bool,
+            neighbors: bool,
+            face_areas: bool,
+            ids: &mut Vec<i32>,
+            positions: &mut Vec<f64>,
+            radii: &mut Vec<f64>,
+            out_volumes: &mut Vec<f64>,
+            offsets: &mut Vec<usize>,
+            out_neighbors: &mut Vec<i32>,
+            out_face_areas: &mut Vec<f64>,
+        ) -> Result<()>;
+        #[rust_name = "compute_all_1"]
+        fn compute_all(
+            con: Pin<&mut container_poly>,
+            volumes: bool,
+            neighbors: bool,
+            face_areas: bool,
+            ids: &mut Vec<i32>,
+            positions: &mut Vec<f64>,
+            radii: &mut Vec<f64>,
+            out_volumes: &mut Vec<f64>,
+            offsets: &mut Vec<usize>,
+            out_neighbors: &mut Vec<i32>,
+            out_face_areas: &mut Vec<f64>,
+        ) -> Result<()>;
+
+        #[rust_name = "container_blocks_0"]
+        fn container_blocks(con: &container) -> i32;
+        #[rust_name = "container_blocks_1"]
+        fn container_blocks(con: &container_poly) -> i32;
+
+        type compute_engine;
+        #[rust_name = "new_compute_engine_0"]
+        fn new_compute_engine(
+            con: &container,
+        ) -> Result<UniquePtr<compute_engine>>;
+        fn compute_block(
+            self: Pin<&mut compute_engine>,
+            ijk: i32,
+            volumes: bool,
+            neighbors: bool,
+            face_areas: bool,
+            ids: &mut Vec<i32>,
+            positions: &mut Vec<f64>,
+            radii: &mut Vec<f64>,
+            out_volumes: &mut Vec<f64>,
+            offsets: &mut Vec<usize>,
+            out_neighbors: &mut Vec<i32>,
+            out_face_areas: &mut Vec<f64>,
+        ) -> Result<()>;
+
+        type compute_engine_poly;
+        #[rust_name = "new_compute_engine_1"]
+        fn new_compute_engine(
+            con: &container_poly,
+        ) -> Result<UniquePtr<compute_engine_poly>>;
+        fn compute_block(
+            self: Pin<&mut compute_engine_poly>,
+            ijk: i32,
+            volumes: bool,
+            neighbors: bool,
+            face_areas: bool,
+            ids: &mut Vec<i32>,
+            positions: &mut Vec<f64>,
+            radii: &mut Vec<f64>,
+            out_volumes: &mut Vec<f64>,
+            offsets: &mut Vec<usize>,
+            out_neighbors: &mut Vec<i32>,
+            out_face_areas: &mut Vec<f64>,
+        ) -> Result<()>;
+    }
+}
+
+use crate::container::{ContainerRad, ContainerStd};
+use crate::error::VoroError;
+#[cfg(feature = "rayon")]
+use cxx::{memory::UniquePtrTarget, UniquePtr};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "rayon")]
+use std::pin::Pin;
+
+type DVec3 = [f64; 3];
+
+/// The properties to compute for each cell in a `CellBatch`.
+///
+/// The ID, position and radius of the particles are always recorded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellProperties {
+    /// Compute the volume of each cell.
+    pub volume: bool,
+    /// Compute the ID of the neighbor on each face. This needs neighbor
+    /// tracking, which makes the computation slower.
+    pub neighbors: bool,
+    /// Compute the area of each face.
+    pub face_areas: bool,
+}
+
+impl CellProperties {
+    /// Select every property.
+    pub fn all() -> Self {
+        Self {
+            volume: true,
+            neighbors: true,
+            face_areas: true,
+        }
+    }
+}
+
+/// The properties of all the Voronoi cells of a container, stored as
+/// struct-of-arrays.
+///
+/// The cells are computed in a single pass inside voro++, which avoids a
+/// round trip across the FFI boundary for each cell and each property.
+/// The cells are stored in the order of the loop over the container, and
+/// particles whose cell could not be computed (for example, because it
+/// was cut away by a wall) are skipped.
+///
+/// The face data uses a compressed sparse row layout: the faces of the
+/// cell `i` are at `face_offsets[i]..face_offsets[i + 1]` of `neighbors`
+/// and `face_areas`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CellBatch {
+    /// The ID of the particle of each cell.
+    pub ids: Vec<i32>,
+    /// The position of the particle of each cell, as consecutive x, y
+    /// and z coordinates.
+    pub positions: Vec<f64>,
+    /// The radius of the particle of each cell.
+    pub radii: Vec<f64>,
+    /// The volume of each cell, if selected.
+    pub volumes: Vec<f64>,
+    /// The start of the faces of each cell, followed by the total number
+    /// of faces. Empty unless `neighbors` or `face_areas` is selected.
+    pub face_offsets: Vec<usize>,
+    /// The ID of the neighbor on each face, if selected.
+    pub neighbors: Vec<i32>,
+    /// The area of each face, if selected.
+    pub face_areas: Vec<f64>,
+}
+
+impl CellBatch {
+    /// Computes the cells of all particles in a `ContainerStd`.
+    ///
+    /// * `container`: the container.
+    /// * `properties`: the properties to compute.
+    pub fn of_container_std(
+        container: &ContainerStd,
+        properties: CellProperties,
+    ) -> Self {
+        Self::try_of_container_std(container, properties)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `of_container_std`.
+    pub fn try_of_container_std(
+        container: &ContainerStd,
+        properties: CellProperties,
+    ) -> Result<Self, VoroError> {
+        let mut batch = Self::default();
+        ffi::compute_all_0(
+            container.inner_pin(),
+            properties.volume,
+            properties.neighbors,
+            properties.face_areas,
+            &mut batch.ids,
+            &mut batch.positions,
+            &mut batch.radii,
+            &mut batch.volumes,
+            &mut batch.face_offsets,
+            &mut batch.neighbors,
+            &mut batch.face_areas,
+        )?;
+        Ok(batch)
+    }
+
+    /// Computes the cells of all particles in a `ContainerRad`.
+    ///
+    /// * `container`: the container.
+    /// * `properties`: the properties to compute.
+    pub fn of_container_rad(
+        container: &ContainerRad,
+        properties: CellProperties,
+    ) -> Self {
+        Self::try_of_container_rad(container, properties)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `of_container_rad`.
+    pub fn try_of_container_rad(
+        container: &ContainerRad,
+        properties: CellProperties,
+    ) -> Result<Self, VoroError> {
+        let mut batch = Self::default();
+        ffi::compute_all_1(
+            container.inner_pin(),
+            properties.volume,
+            properties.neighbors,
+            properties.face_areas,
+            &mut batch.ids,
+            &mut batch.positions,
+            &mut batch.radii,
+            &mut batch.volumes,
+            &mut batch.face_offsets,
+            &mut batch.neighbors,
+            &mut batch.face_areas,
+        )?;
+        Ok(batch)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn try_compute_par<E: Engine>(
+        container: &E::Container,
+        properties: CellProperties,
+    ) -> Result<Self, VoroError> {
+        let con = SharedContainer(container);
+        let blocks = E::blocks(con.get()) as usize;
+        // one contiguous range of blocks per thread, since creating an
+        // engine copies the container and allocates its search mask.
+        let threads = rayon::current_num_threads();
+        let chunk = blocks.div_ceil(threads).max(1);
+        let batches = (0..blocks)
+            .step_by(chunk)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|start| {
+                let mut engine = E::new(con.get())?;
+                let mut batch = Self::default();
+                for ijk in start..blocks.min(start + chunk)
+                {
+                    engine.pin_mut().compute_block_into(
+                        ijk as i32, properties, &mut batch,
+                    )?;
+                }
+                Ok(batch)
+            })
+            .collect::<Result<Vec<_>, VoroError>>()?;
+
+        let mut batch = Self::default();
+        for other in batches {
+            batch.append(other);
+        }
+        Ok(batch.sorted_by_id())
+    }
+
+    /// Appends the cells of another batch computed with the same
+    /// properties.
+    #[cfg(feature = "rayon")]
+    fn append(&mut self, other: Self) {
+        if let Some((_, offsets)) =
+            other.face_offsets.split_first()
+        {
+            let base = self
+                .face_offsets
+                .last()
+                .copied()
+                .unwrap_or(0);
+            if self.face_offsets.is_empty() {
+                self.face_offsets.push(0);
+            }
+            self.face_offsets
+                .extend(offsets.iter().map(|o| o + base));
+        }
+        self.ids.extend(other.ids);
+        self.positions.extend(other.positions);
+        self.radii.extend(other.radii);
+        self.volumes.extend(other.volumes);
+        self.neighbors.extend(other.neighbors);
+        self.face_areas.extend(other.face_areas);
+    }
+
+    /// Return the same cells, sorted by particle ID.
+    #[cfg(feature = "rayon")]
+    fn sorted_by_id(self) -> Self {
+        let mut order: Vec<usize> =
+            (0..self.len()).collect();
+        order.sort_by_key(|&i| self.ids[i]);
+
+        let mut sorted = Self {
+            ids: Vec::with_capacity(self.ids.len()),
+            positions: Vec::with_capacity(
+                self.positions.len(),
+            ),
+            radii: Vec::with_capacity(self.radii.len()),
+            volumes: Vec::with_capacity(self.volumes.len()),
+            face_offsets: Vec::with_capacity(
+                self.face_offsets.len(),
+            ),
+            neighbors: Vec::with_capacity(
+                self.neighbors.len(),
+            ),
+            face_areas: Vec::with_capacity(
+                self.face_areas.len(),
+            ),
+        };
+        if !self.face_offsets.is_empty() {
+            sorted.face_offsets.push(0);
+        }
+        for i in order {
+            sorted.ids.push(self.ids[i]);
+            sorted.positions.extend_from_slice(
+                &self.positions[3 * i..3 * i + 3],
+            );
+            sorted.radii.push(self.radii[i]);
+            if !self.volumes.is_empty() {
+                sorted.volumes.push(self.volumes[i]);
+            }
+            if !self.face_offsets.is_empty() {
+                let faces = self.face_offsets[i]
+                    ..self.face_offsets[i + 1];
+                let end =
+                    sorted.face_offsets.last().unwrap()
+                        + faces.len();
+                if !self.neighbors.is_empty() {
+                    sorted.neighbors.extend_from_slice(
+                        &self.neighbors[faces.clone()],
+                    );
+                }
+                if !self.face_areas.is_empty() {
+                    sorted.face_areas.extend_from_slice(
+                        &self.face_areas[faces],
+                    );
+                }
+                sorted.face_offsets.push(end);
+            }
+        }
+        sorted
+    }
+
+    /// Return the number of cells.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Return true if there is no cell.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Return the position of the particle of the cell `i`.
+    pub fn position(&self, i: usize) -> DVec3 {
+        let p = &self.positions[3 * i..3 * i + 3];
+        [p[0], p[1], p[2]]
+    }
+
+    /// Return the neighbor IDs of the faces of the cell `i`.
+    ///
+    /// Return an empty slice if `neighbors` was not selected.
+    pub fn neighbors_of(&self, i: usize) -> &[i32] {
+        if self.neighbors.is_empty() {
+            return &[];
+        }
+        &self.neighbors
+            [self.face_offsets[i]..self.face_offsets[i + 1]]
+    }
+
+    /// Return the areas of the faces of the cell `i`.
+    ///
+    /// Return an empty slice if `face_areas` was not selected.
+    pub fn face_areas_of(&self, i: usize) -> &[f64] {
+        if self.face_areas.is_empty() {
+            return &[];
+        }
+        &self.face_areas
+            [self.face_offsets[i]..self.face_offsets[i + 1]]
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ContainerStd<'_> {
+    /// Computes the cells of all particles on the rayon thread pool.
+    ///
+    /// * `properties`: the properties to compute.
+    ///
+    /// The grid blocks of the container are split into one contiguous
+    /// range per thread, and each thread computes the cells of its range
+    /// with its own compute engine. Unlike `CellBatch::of_container_std`,
+    /// the cells are sorted by particle ID.
+    ///
+    /// A container with a `WallCustom` is computed on the current
+    /// thread, since the wall may not be called from several threads at
+    /// once.
+    pub fn compute_all_par(
+        &self,
+        properties: CellProperties,
+    ) -> CellBatch {
+        self.try_compute_all_par(properties)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `compute_all_par`.
+    pub fn try_compute_all_par(
+        &self,
+        properties: CellProperties,
+    ) -> Result<CellBatch, VoroError> {
+        if self.has_custom_walls {
+            return CellBatch::try_of_container_std(
+                self, properties,
+            )
+            .map(CellBatch::sorted_by_id);
+        }
+
+        CellBatch::try_compute_par::<ffi::compute_engine>(
+            self.inner.as_ref().unwrap(),
+            properties,
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ContainerRad<'_> {
+    /// Computes the cells of all particles on the rayon thread pool.
+    ///
+    /// * `properties`: the properties to compute.
+    ///
+    /// See `ContainerStd::compute_all_par`.
+    pub fn compute_all_par(
+        &self,
+        properties: CellProperties,
+    ) -> CellBatch {
+        self.try_compute_all_par(properties)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `compute_all_par`.
+    pub fn try_compute_all_par(
+        &self,
+        properties: CellProperties,
+    ) -> Result<CellBatch, VoroError> {
+        if self.has_custom_walls {
+            return CellBatch::try_of_container_rad(
+                self, properties,
+            )
+            .map(CellBatch::sorted_by_id);
+        }
+
+        CellBatch::try_compute_par::<ffi::compute_engine_poly>(
+            self.inner.as_ref().unwrap(),
+            properties,
+        )
+    }
+}
+
+/// The compute engine of one kind of container.
+#[cfg(feature = "rayon")]
+trait Engine: UniquePtrTarget + Send + Sized {
+    type Container;
+
+    /// Return the number of grid blocks of the container.
+    fn blocks(con: &Self::Container) -> i32;
+
+    /// Create an engine computing the cells of the container.
+    fn new(
+        con: &Self::Container,
+    ) -> Result<UniquePtr<Self>, VoroError>;
+
+    /// Append the cells of the grid block `ijk` to the batch.
+    fn compute_block_into(
+        self: Pin<&mut Self>,
+        ijk: i32,
+        properties: CellProperties,
+        batch: &mut CellBatch,
+    ) -> Result<(), VoroError>;
+}
+
+#[cfg(feature = "rayon")]
+impl Engine for ffi::compute_engine {
+    type Container = ffi::container;
+
+    fn blocks(con: &ffi::container) -> i32 {
+        ffi::container_blocks_0(con)
+    }
+
+    fn new(
+        con: &ffi::container,
+    ) -> Result<UniquePtr<Self>, VoroError> {
+        Ok(ffi::new_compute_engine_0(con)?)
+    }
+
+    fn compute_block_into(
+        self: Pin<&mut Self>,
+        ijk: i32,
+        properties: CellProperties,
+        batch: &mut CellBatch,
+    ) -> Result<(), VoroError> {
+        self.compute_block(
+            ijk,
+            properties.volume,
+            properties.neighbors,
+            properties.face_areas,
+            &mut batch.ids,
+            &mut batch.positions,
+            &mut batch.radii,
+            &mut batch.volumes,
+            &mut batch.face_offsets,
+            &mut batch.neighbors,
+            &mut batch.face_areas,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Engine for ffi::compute_engine_poly {
+    type Container = ffi::container_poly;
+
+    fn blocks(con: &ffi::container_poly) -> i32 {
+        ffi::container_blocks_1(con)
+    }
+
+    fn new(
+        con: &ffi::container_poly,
+    ) -> Result<UniquePtr<Self>, VoroError> {
+        Ok(ffi::new_compute_engine_1(con)?)
+    }
+
+    fn compute_block_into(
+        self: Pin<&mut Self>,
+        ijk: i32,
+        properties: CellProperties,
+        batch: &mut CellBatch,
+    ) -> Result<(), VoroError> {
+        self.compute_block(
+            ijk,
+            properties.volume,
+            properties.neighbors,
+            properties.face_areas,
+            &mut batch.ids,
+            &mut batch.positions,
+            &mut batch.radii,
+            &mut batch.volumes,
+            &mut batch.face_offsets,
+            &mut batch.neighbors,
+            &mut batch.face_areas,
+        )?;
+        Ok(())
+    }
+}
+
+/// A container shared by the compute engines of several threads.
+#[cfg(feature = "rayon")]
+struct SharedContainer<'c, C>(&'c C);
+
+// SAFETY: the compute engines only read the particles and walls of the
+// container, each through its own copy of the container, and
+// `WallCustom` is the only wall with mutable state.
+#[cfg(feature = "rayon")]
+unsafe impl<C> Sync for SharedContainer<'_, C> {}
+
+#[cfg(feature = "rayon")]
+impl<'c, C> SharedContainer<'c, C> {
+    // a method, so that closures capture the whole `Sync` struct.
+    fn get(&self) -> &'c C {
+        self.0
+    }
+}
+
+// SAFETY: an engine is only used by one thread at a time, and the
+// container it refers to outlives it.
+#[cfg(feature = "rayon")]
+unsafe impl Send for ffi::compute_engine {}
+
+// SAFETY: as for `compute_engine`.
+#[cfg(feature = "rayon")]
+unsafe impl Send for ffi::compute_engine_poly {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn batch_test() {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, 0.0], 0.0);
+        con.put(1, [0.5, 0.0, 0.0], 0.0);
+
+        let batch = CellBatch::of_container_std(
+            &con,
+            CellProperties::all(),
+        );
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch.volumes, vec![4.0, 4.0]);
+        assert_eq!(batch.face_offsets, vec![0, 6, 12]);
+        for i in 0..batch.len() {
+            let other = 1 - batch.ids[i];
+            assert!(batch.neighbors_of(i).contains(&other));
+            let area: f64 =
+                batch.face_areas_of(i).iter().sum();
+            assert!((area - 16.0).abs() < 1e-10);
+        }
+
+        let batch = CellBatch::of_container_std(
+            &con,
+            CellProperties {
+                volume: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(batch.volumes.len(), 2);
+        assert!(batch.face_offsets.is_empty());
+        assert!(batch.neighbors_of(0).is_empty());
+    }
+
+    #[test]
+    fn batch_rad_test() {
+        let mut con = ContainerRad::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, 0.0], 0.2);
+        con.put(1, [0.5, 0.0, 0.0], 0.4);
+
+        let batch = CellBatch::of_container_rad(
+            &con,
+            CellProperties {
+                face_areas: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(batch.len(), 2);
+        assert!(batch.volumes.is_empty());
+        assert!(batch.neighbors.is_empty());
+        assert_eq!(batch.face_areas.len(), 12);
+        let i = batch
+            .ids
+            .iter()
+            .position(|&id| id == 1)
+            .unwrap();
+        assert_eq!(batch.position(i), [0.5, 0.0, 0.0]);
+        assert_eq!(batch.radii[i], 0.4);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn batch_par_test() {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [4, 4, 4],
+            [false, false, false],
+        );
+        for i in 0..64 {
+            let x = |k: i32| -0.75 + 0.5 * k as f64;
+            con.put(
+                63 - i,
+                [x(i % 4), x(i / 4 % 4), x(i / 16)],
+                0.0,
+            );
+        }
+
+        let seq = CellBatch::of_container_std(
+            &con,
+            CellProperties::all(),
+        );
+        let par =
+            con.compute_all_par(CellProperties::all());
+        assert_eq!(par.len(), 64);
+        assert_eq!(par.ids, (0..64).collect::<Vec<_>>());
+        assert_eq!(
+            *par.face_offsets.last().unwrap(),
+            6 * 64
+        );
+        for i in 0..par.len() {
+            let j = seq
+                .ids
+                .iter()
+                .position(|&id| id == par.ids[i]);
+            let j = j.unwrap();
+            assert_eq!(par.position(i), seq.position(j));
+            assert_eq!(par.volumes[i], seq.volumes[j]);
+            assert_eq!(
+                par.neighbors_of(i),
+                seq.neighbors_of(j)
+            );
+            assert_eq!(
+                par.face_areas_of(i),
+                seq.face_areas_of(j)
+            );
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn batch_rad_par_test() {
+        let mut con = ContainerRad::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [4, 4, 4],
+            [false, false, false],
+        );
+        for i in 0..64 {
+            let x = |k: i32| -0.75 + 0.5 * k as f64;
+            con.put(
+                i,
+                [x(i % 4), x(i / 4 % 4), x(i / 16)],
+                0.05 + 0.002 * i as f64,
+            );
+        }
+
+        let seq = CellBatch::of_container_rad(
+            &con,
+            CellProperties::all(),
+        );
+        let par =
+            con.compute_all_par(CellProperties::all());
+        assert_eq!(par.len(), 64);
+        assert_eq!(par.ids, (0..64).collect::<Vec<_>>());
+        let total: f64 = par.volumes.iter().sum();
+        assert!((total - 8.0).abs() < 1e-10);
+        for i in 0..par.len() {
+            let j = seq
+                .ids
+                .iter()
+                .position(|&id| id == par.ids[i]);
+            let j = j.unwrap();
+            assert_eq!(par.position(i), seq.position(j));
+            assert_eq!(par.radii[i], seq.radii[j]);
+            assert_eq!(par.volumes[i], seq.volumes[j]);
+            assert_eq!(
+                par.neighbors_of(i),
+                seq.neighbors_of(j)
+            );
+            assert_eq!(
+                par.face_areas_of(i),
+                seq.face_areas_of(j)
+            );
+        }
+    }
+}
@@ -1,5 +1,10 @@
 #include <memory>
+#include <vector>
+#include "rust/cxx.h"
 #include "../voro/src/voro++.hh"
+#include "wall_shapes.hh"
+#include "batch.hh"
+#include "draw.hh"
 
 namespace voro
 {
@@ -18,6 +23,37 @@ namespace voro
         return c1;
     }
 
+    // Lists the vertices joined to each vertex of a cell by an edge, read
+    // from the edge table. Each vertex is written as its order followed by
+    // the indices of its neighbors.
+    template <typename T>
+    inline void vertex_neighbors(const T &c, std::vector<int> &v)
+    {
+        v.clear();
+        for (int i = 0; i < c.p; i++)
+        {
+            v.push_back(c.nu[i]);
+            for (int j = 0; j < c.nu[i]; j++)
+                v.push_back(c.ed[i][j]);
+        }
+    }
+
+    // Applies the linear map m, given row by row, to the vertices of a
+    // cell. Like translate(), this works on pts directly, which holds
+    // three coordinates per vertex at twice their values.
+    template <typename T>
+    inline void transform_cell(T &c, rust::Slice<const double> m)
+    {
+        for (int i = 0; i < c.p; i++)
+        {
+            double *pp = c.pts + 3 * i;
+            double x = pp[0], y = pp[1], z = pp[2];
+            pp[0] = m[0] * x + m[1] * y + m[2] * z;
+            pp[1] = m[3] * x + m[4] * y + m[5] * z;
+            pp[2] = m[6] * x + m[7] * y + m[8] * z;
+        }
+    }
+
     template <typename T>
     inline std::unique_ptr<T> clone_wall(std::unique_ptr<T> const &w0)
     {
@@ -25,6 +61,41 @@ namespace voro
         return std::make_unique<T>(*w0);
     }
 
+    struct WallFn;
+
+    // A wall whose geometry is defined on the Rust side. Every virtual
+    // method forwards to a Rust callback, together with the opaque
+    // `context` that holds the Rust wall.
+    class wall_custom : public wall
+    {
+    public:
+        wall_custom(
+            rust::Fn<bool(WallFn *, double, double, double)> point_inside_,
+            rust::Fn<bool(WallFn *, voronoicell *, double, double, double)> cut_cell_0_,
+            rust::Fn<bool(WallFn *, voronoicell_neighbor *, double, double, double)> cut_cell_1_,
+            WallFn *context_)
+            : f_point_inside(point_inside_), f_cut_cell_0(cut_cell_0_),
+              f_cut_cell_1(cut_cell_1_), context(context_) {}
+        bool point_inside(double x, double y, double z)
+        {
+            return f_point_inside(context, x, y, z);
+        }
+        bool cut_cell(voronoicell &c, double x, double y, double z)
+        {
+            return f_cut_cell_0(context, &c, x, y, z);
+        }
+        bool cut_cell(voronoicell_neighbor &c, double x, double y, double z)
+        {
+            return f_cut_cell_1(context, &c, x, y, z);
+        }
+
+    private:
+        rust::Fn<bool(WallFn *, double, double, double)> f_point_inside;
+        rust::Fn<bool(WallFn *, voronoicell *, double, double, double)> f_cut_cell_0;
+        rust::Fn<bool(WallFn *, voronoicell_neighbor *, double, double, double)> f_cut_cell_1;
+        WallFn *context;
+    };
+
     inline wall &wall_sphere_to_wall(wall_sphere &w)
     {
         return w;
@@ -41,6 +112,26 @@ namespace voro
     {
         return w;
     }
+    inline wall &wall_ellipsoid_to_wall(wall_ellipsoid &w)
+    {
+        return w;
+    }
+    inline wall &wall_torus_to_wall(wall_torus &w)
+    {
+        return w;
+    }
+    inline wall &wall_box_to_wall(wall_box &w)
+    {
+        return w;
+    }
+    inline wall &wall_polyhedron_to_wall(wall_polyhedron &w)
+    {
+        return w;
+    }
+    inline wall &wall_custom_to_wall(wall_custom &w)
+    {
+        return w;
+    }
 
     inline wall_list &container_to_wall_list(container &c)
     {
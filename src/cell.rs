@@ -15,6 +15,14 @@ pub mod ffi {
         fn clone_voronoicell(
             value: &UniquePtr<voronoicell>,
         ) -> UniquePtr<voronoicell>;
+        fn vertex_neighbors(
+            c: &voronoicell,
+            v: Pin<&mut CxxVector<i32>>,
+        );
+        fn transform_cell(
+            c: Pin<&mut voronoicell>,
+            m: &[f64],
+        );
         fn init(
             self: Pin<&mut voronoicell>,
             xmin: f64,
@@ -149,14 +157,14 @@ pub mod ffi {
             z: f64,
             rsq: f64,
             p_id: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         fn nplane(
             self: Pin<&mut voronoicell>,
             x: f64,
             y: f64,
             z: f64,
             p_id: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "plane_rsq"]
         fn plane(
             self: Pin<&mut voronoicell>,
@@ -164,13 +172,13 @@ pub mod ffi {
             y: f64,
             z: f64,
             rsq: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         fn plane(
             self: Pin<&mut voronoicell>,
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         type voronoicell_neighbor;
         #[rust_name = "new_voronoicell_neighbor"]
@@ -316,14 +324,14 @@ pub mod ffi {
             z: f64,
             rsq: f64,
             p_id: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         fn nplane(
             self: Pin<&mut voronoicell_neighbor>,
             x: f64,
             y: f64,
             z: f64,
             p_id: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "plane_rsq"]
         fn plane(
             self: Pin<&mut voronoicell_neighbor>,
@@ -331,24 +339,40 @@ pub mod ffi {
             y: f64,
             z: f64,
             rsq: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         fn plane(
             self: Pin<&mut voronoicell_neighbor>,
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         fn neighbors(
             self: Pin<&mut voronoicell_neighbor>,
             v: Pin<&mut CxxVector<i32>>,
         );
+        #[rust_name = "vertex_neighbors_neighbor"]
+        fn vertex_neighbors(
+            c: &voronoicell_neighbor,
+            v: Pin<&mut CxxVector<i32>>,
+        );
+        #[rust_name = "transform_cell_neighbor"]
+        fn transform_cell(
+            c: Pin<&mut voronoicell_neighbor>,
+            m: &[f64],
+        );
     }
 }
 
+use crate::custom_format::CustomFormat;
+use crate::draw::{ffi as draw, write_output};
+use crate::error::{check_box, VoroError, VoroErrorKind};
+use crate::slice::{slice_cell, Plane, Polygon};
 use cxx::{CxxVector, UniquePtr};
+use std::io::{self, Write};
 use std::sync::Arc;
 
 type DVec3 = [f64; 3];
+type DMat3 = [[f64; 3]; 3];
 
 pub struct RhoFn {
     f: Arc<dyn Fn(DVec3, DVec3, DVec3) -> f64>,
@@ -378,12 +402,25 @@ impl VoroCellSgl {
     /// * `xyz_min`: the minimum xyz coordinates.
     /// * `xyz_max`: the maximum xyz coordinates.
     pub fn new(xyz_min: DVec3, xyz_max: DVec3) -> Self {
+        Self::try_new(xyz_min, xyz_max)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new`.
+    ///
+    /// Return an error if `xyz_min` and `xyz_max` do not span a non-empty
+    /// box.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
         let mut val = Self::new_empty();
         val.inner.pin_mut().init(
             xyz_min[0], xyz_max[0], xyz_min[1], xyz_max[1],
             xyz_min[2], xyz_max[2],
         );
-        val
+        Ok(val)
     }
 
     /// Initializes the cell to be an octahedron with vertices at
@@ -416,6 +453,46 @@ impl VoroCellSgl {
         );
         val
     }
+
+    /// Initializes the cell to be the convex polyhedron given by the
+    /// intersection of the half-spaces `n.x <= a`. With neighbor
+    /// tracking, the face made by the half-space at index i has the ID i.
+    ///
+    /// * `planes`: the normal `n` and the offset `a` of each half-space.
+    pub fn from_half_spaces(
+        planes: &[(DVec3, f64)],
+    ) -> Self {
+        Self::try_from_half_spaces(planes)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `from_half_spaces`.
+    ///
+    /// Return an error if a normal is zero, or if the half-spaces do not
+    /// bound a non-empty polyhedron.
+    pub fn try_from_half_spaces(
+        planes: &[(DVec3, f64)],
+    ) -> Result<Self, VoroError> {
+        cut_half_spaces(Self::try_new, planes)
+    }
+
+    /// Initializes the cell to be the convex hull of points.
+    ///
+    /// * `points`: the points, of which at least four must not be
+    ///   coplanar.
+    pub fn from_points(points: &[DVec3]) -> Self {
+        Self::try_from_points(points)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `from_points`.
+    ///
+    /// Return an error if the points do not span a volume.
+    pub fn try_from_points(
+        points: &[DVec3],
+    ) -> Result<Self, VoroError> {
+        hull_of_points(Self::try_new, points)
+    }
 }
 
 /// `voronoicell_neighbor` class in voro++.
@@ -440,12 +517,25 @@ impl VoroCellNbr {
     /// * `xyz_min`: the minimum xyz coordinates.
     /// * `xyz_max`: the maximum xyz coordinates.
     pub fn new(xyz_min: DVec3, xyz_max: DVec3) -> Self {
+        Self::try_new(xyz_min, xyz_max)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new`.
+    ///
+    /// Return an error if `xyz_min` and `xyz_max` do not span a non-empty
+    /// box.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
         let mut val = Self::new_empty();
         val.inner.pin_mut().init(
             xyz_min[0], xyz_max[0], xyz_min[1], xyz_max[1],
             xyz_min[2], xyz_max[2],
         );
-        val
+        Ok(val)
     }
 
     /// Initializes the cell to be an octahedron with vertices at
@@ -479,6 +569,46 @@ impl VoroCellNbr {
         val
     }
 
+    /// Initializes the cell to be the convex polyhedron given by the
+    /// intersection of the half-spaces `n.x <= a`. With neighbor
+    /// tracking, the face made by the half-space at index i has the ID i.
+    ///
+    /// * `planes`: the normal `n` and the offset `a` of each half-space.
+    pub fn from_half_spaces(
+        planes: &[(DVec3, f64)],
+    ) -> Self {
+        Self::try_from_half_spaces(planes)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `from_half_spaces`.
+    ///
+    /// Return an error if a normal is zero, or if the half-spaces do not
+    /// bound a non-empty polyhedron.
+    pub fn try_from_half_spaces(
+        planes: &[(DVec3, f64)],
+    ) -> Result<Self, VoroError> {
+        cut_half_spaces(Self::try_new, planes)
+    }
+
+    /// Initializes the cell to be the convex hull of points.
+    ///
+    /// * `points`: the points, of which at least four must not be
+    ///   coplanar.
+    pub fn from_points(points: &[DVec3]) -> Self {
+        Self::try_from_points(points)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `from_points`.
+    ///
+    /// Return an error if the points do not span a volume.
+    pub fn try_from_points(
+        points: &[DVec3],
+    ) -> Result<Self, VoroError> {
+        hull_of_points(Self::try_new, points)
+    }
+
     pub fn neighbors(&mut self) -> Vec<i32> {
         let mut v = CxxVector::new();
         self.inner.pin_mut().neighbors(v.pin_mut());
@@ -504,6 +634,210 @@ impl Clone for VoroCellNbr {
     }
 }
 
+// SAFETY: a Voronoi cell owns all of its memory, and voro++ keeps no
+// global state for it. The only method taking `&self` is `clone`, which
+// only reads the cell.
+unsafe impl Send for VoroCellSgl {}
+unsafe impl Sync for VoroCellSgl {}
+unsafe impl Send for VoroCellNbr {}
+unsafe impl Sync for VoroCellNbr {}
+
+/// Decodes lists that are each written as their length followed by
+/// their elements.
+fn decode_lists(v: &[i32]) -> Vec<Vec<usize>> {
+    let mut lists = Vec::new();
+    let mut k = 0;
+    while k < v.len() {
+        let n = v[k] as usize;
+        lists.push(
+            v[k + 1..k + 1 + n]
+                .iter()
+                .map(|&i| i as usize)
+                .collect(),
+        );
+        k += n + 1;
+    }
+    lists
+}
+
+/// Checks that a matrix is finite and has a positive determinant, and
+/// flattens it row by row.
+fn check_transform(
+    m: DMat3,
+) -> Result<[f64; 9], VoroError> {
+    let det = m[0][0]
+        * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    if !(det > 0.0 && det.is_finite()) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            "the matrix must be finite with a positive determinant",
+        ));
+    }
+    let [a, b, c] = m;
+    Ok([
+        a[0], a[1], a[2], b[0], b[1], b[2], c[0], c[1],
+        c[2],
+    ])
+}
+
+/// The half-width of the box that a polyhedron is cut from, relative to
+/// the size of its input. A polyhedron that reaches half of this box is
+/// considered unbounded.
+const UNBOUNDED_SCALE: f64 = 1e4;
+
+/// Cuts a box down to the intersection of the half-spaces `n.x <= a`.
+/// The face made by the half-space at index i has the plane ID i.
+fn cut_half_spaces<T: VoroCell>(
+    new: fn(DVec3, DVec3) -> Result<T, VoroError>,
+    planes: &[(DVec3, f64)],
+) -> Result<T, VoroError> {
+    cut_half_spaces_within(new, planes, f64::INFINITY)
+}
+
+/// Same as `cut_half_spaces`, but the box is sized for half-spaces at a
+/// distance of at most `max_scale` from the origin. The farther ones
+/// still cut the box, and the intersection is unbounded if it reaches
+/// beyond this size.
+fn cut_half_spaces_within<T: VoroCell>(
+    new: fn(DVec3, DVec3) -> Result<T, VoroError>,
+    planes: &[(DVec3, f64)],
+    max_scale: f64,
+) -> Result<T, VoroError> {
+    let mut scale = 1.0f64;
+    let mut unit = Vec::with_capacity(planes.len());
+    for &(n, a) in planes {
+        let l = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2])
+            .sqrt();
+        if !(l > 0.0 && l.is_finite() && a.is_finite()) {
+            return Err(VoroError::new(
+                VoroErrorKind::InvalidArgument,
+                "a half-space has a zero or non-finite normal",
+            ));
+        }
+        scale = scale.max(a.abs() / l);
+        unit.push(([n[0] / l, n[1] / l, n[2] / l], a / l));
+    }
+
+    let b = UNBOUNDED_SCALE * scale.min(max_scale).max(1.0);
+    let mut cell = new([-b, -b, -b], [b, b, b])?;
+    for (i, &(n, a)) in unit.iter().enumerate() {
+        if !cell.try_nplane_rsq(n, 2.0 * a, i as i32)? {
+            return Err(VoroError::new(
+                VoroErrorKind::InvalidArgument,
+                "the half-spaces have an empty intersection",
+            ));
+        }
+    }
+    let unbounded = cell
+        .vertices()
+        .iter()
+        .any(|v| v.iter().any(|x| x.abs() >= 0.5 * b));
+    if unbounded {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            "the half-spaces do not bound a polyhedron",
+        ));
+    }
+    Ok(cell)
+}
+
+/// Builds the convex hull of points. The facets are found as the
+/// vertices of the polar dual of the points around their mean, which is
+/// itself computed by cutting a cell.
+fn hull_of_points<T: VoroCell>(
+    new: fn(DVec3, DVec3) -> Result<T, VoroError>,
+    points: &[DVec3],
+) -> Result<T, VoroError> {
+    let degenerate = || {
+        VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            "the points do not span a volume",
+        )
+    };
+    if points.iter().flatten().any(|x| !x.is_finite()) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            "a point is not finite",
+        ));
+    }
+    if points.len() < 4 {
+        return Err(degenerate());
+    }
+
+    let k = 1.0 / points.len() as f64;
+    let mut c = [0.0; 3];
+    for p in points {
+        for i in 0..3 {
+            c[i] += k * p[i];
+        }
+    }
+    let q: Vec<DVec3> = points
+        .iter()
+        .map(|p| [p[0] - c[0], p[1] - c[1], p[2] - c[2]])
+        .collect();
+    let r = q
+        .iter()
+        .map(|q| {
+            (q[0] * q[0] + q[1] * q[1] + q[2] * q[2]).sqrt()
+        })
+        .fold(0.0, f64::max);
+    if r == 0.0 {
+        return Err(degenerate());
+    }
+
+    // The points are scaled into the unit ball, so that the dual contains
+    // the unit ball and is bounded exactly when the mean is inside the hull.
+    let dual: Vec<(DVec3, f64)> = q
+        .iter()
+        .filter(|q| q.iter().any(|&x| x != 0.0))
+        .map(|q| ([q[0] / r, q[1] / r, q[2] / r], 1.0))
+        .collect();
+    // A point close to the mean has a dual half-space far away, which
+    // would blow up the box past the tolerances of voro++. The box is
+    // first sized for the unit ball, which holds the dual unless a facet
+    // is very close to the mean.
+    let facets = cut_half_spaces_within(
+        VoroCellSgl::try_new,
+        &dual,
+        1.0,
+    )
+    .or_else(|_| {
+        cut_half_spaces(VoroCellSgl::try_new, &dual)
+    })
+    .map_err(|_| degenerate())?
+    .vertices();
+
+    let planes: Vec<(DVec3, f64)> = facets
+        .iter()
+        .map(|w| {
+            (
+                *w,
+                r + w[0] * c[0] + w[1] * c[1] + w[2] * c[2],
+            )
+        })
+        .collect();
+    cut_half_spaces(new, &planes)
+}
+
+/// A face of a Voronoi cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Face {
+    /// The indices of the vertices of the face into the vertices of its
+    /// cell, ordered counterclockwise when seen from outside.
+    pub vertices: Vec<usize>,
+    /// The ID of the particle or wall on the other side of the face, if
+    /// the cell tracks neighbors.
+    pub neighbor: Option<i32>,
+    /// The area of the face.
+    pub area: f64,
+    /// The perimeter of the face.
+    pub perimeter: f64,
+    /// The outward unit normal of the face.
+    pub normal: DVec3,
+}
+
 /// `voronoicell_base` abstract class in voro++.
 ///
 /// A trait representing a single Voronoi cell.
@@ -521,6 +855,102 @@ pub trait VoroCell {
     /// * `xyz`: the coordinates of the vector.
     fn translate(&mut self, xyz: DVec3);
 
+    /// Applies a linear map to the vertices of the Voronoi cell, keeping
+    /// its topology and neighbor information.
+    ///
+    /// * `m`: the matrix of the map, row by row.
+    ///
+    /// Panics if the map is not orientation-preserving, see
+    /// `try_transform`.
+    fn transform(&mut self, m: DMat3) {
+        self.try_transform(m)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `transform`.
+    ///
+    /// Return an error if the matrix is not finite or its determinant
+    /// is not positive, since a reflection would turn the cell inside
+    /// out.
+    fn try_transform(
+        &mut self,
+        m: DMat3,
+    ) -> Result<(), VoroError>;
+
+    /// Rotates the Voronoi cell about the origin.
+    ///
+    /// * `m`: the rotation matrix, row by row.
+    ///
+    /// Panics if `m` is not a rotation, see `try_rotate`.
+    fn rotate(&mut self, m: DMat3) {
+        self.try_rotate(m).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `rotate`.
+    ///
+    /// Return an error if `m` is not orthonormal with determinant 1.
+    fn try_rotate(
+        &mut self,
+        m: DMat3,
+    ) -> Result<(), VoroError> {
+        for i in 0..3 {
+            for j in 0..3 {
+                let d = m[i][0] * m[j][0]
+                    + m[i][1] * m[j][1]
+                    + m[i][2] * m[j][2];
+                let e = if i == j { 1.0 } else { 0.0 };
+                if (d - e).abs() > 1e-9 {
+                    return Err(VoroError::new(
+                        VoroErrorKind::InvalidArgument,
+                        "the matrix is not a rotation",
+                    ));
+                }
+            }
+        }
+        self.try_transform(m)
+    }
+
+    /// Rotates the Voronoi cell about the origin by a quaternion, which
+    /// is normalized first.
+    ///
+    /// * `q`: the quaternion as `[w, x, y, z]`.
+    ///
+    /// Panics if `q` is zero or not finite.
+    fn rotate_quaternion(&mut self, q: [f64; 4]) {
+        let l = q.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let [w, x, y, z] = q.map(|v| v / l);
+        self.rotate([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ]);
+    }
+
+    /// Scales the Voronoi cell about the origin.
+    ///
+    /// * `s`: the scale factor.
+    ///
+    /// Panics if `s` is not positive and finite.
+    fn scale(&mut self, s: f64) {
+        self.transform([
+            [s, 0.0, 0.0],
+            [0.0, s, 0.0],
+            [0.0, 0.0, s],
+        ]);
+    }
+
     /// Calculates the volume of the Voronoi cell, by decomposing the cell into
     /// tetrahedra extending outward from the zeroth vertex, whose volumes are
     /// evaluated using a scalar triple product.
@@ -588,6 +1018,39 @@ pub trait VoroCell {
     /// containing a list of all the vertices that make up that face.
     fn face_vertices(&mut self) -> Vec<i32>;
 
+    /// Returns, for each vertex, the indices of the vertices that are
+    /// joined to it by an edge.
+    fn vertex_neighbors(&mut self) -> Vec<Vec<usize>>;
+
+    /// Returns the edges of the cell as pairs of vertex indices, with
+    /// the smaller index first. Each edge is listed once.
+    fn edges(&mut self) -> Vec<(usize, usize)> {
+        self.vertex_neighbors()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, n)| {
+                n.into_iter()
+                    .filter(move |&j| i < j)
+                    .map(move |j| (i, j))
+            })
+            .collect()
+    }
+
+    /// Returns the lengths of the edges, in the same order as `edges`.
+    fn edge_lengths(&mut self) -> Vec<f64> {
+        let vertices = self.vertices();
+        self.edges()
+            .into_iter()
+            .map(|(i, j)| {
+                let (a, b) = (vertices[i], vertices[j]);
+                let d =
+                    [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+                (d[0] * d[0] + d[1] * d[1] + d[2] * d[2])
+                    .sqrt()
+            })
+            .collect()
+    }
+
     /// This routine returns the perimeters of each face.
     fn face_perimeters(&mut self) -> Vec<f64>;
 
@@ -596,6 +1059,189 @@ pub trait VoroCell {
     /// that plane.
     fn normals(&mut self) -> Vec<f64>;
 
+    /// Returns the ID of the neighbor on the other side of each face, in
+    /// the same order as `face_areas`, or `None` if the cell does not
+    /// track neighbors.
+    fn face_neighbors(&mut self) -> Option<Vec<i32>>;
+
+    /// Returns the vertices of the cell in the local coordinate system.
+    fn vertices(&mut self) -> Vec<DVec3> {
+        self.vertices_local()
+            .chunks_exact(3)
+            .map(|v| [v[0], v[1], v[2]])
+            .collect()
+    }
+
+    /// Returns the faces of the cell, decoded from `face_vertices`,
+    /// `face_areas`, `face_perimeters`, `normals` and `face_neighbors`.
+    /// The vertex indices refer to `vertices`.
+    fn faces(&mut self) -> Vec<Face> {
+        let encoded = self.face_vertices();
+        let areas = self.face_areas();
+        let perimeters = self.face_perimeters();
+        let normals = self.normals();
+        let neighbors = self.face_neighbors();
+
+        decode_lists(&encoded)
+            .into_iter()
+            .enumerate()
+            .map(|(i, vertices)| Face {
+                vertices,
+                neighbor: neighbors.as_ref().map(|v| v[i]),
+                area: areas[i],
+                perimeter: perimeters[i],
+                normal: [
+                    normals[3 * i],
+                    normals[3 * i + 1],
+                    normals[3 * i + 2],
+                ],
+            })
+            .collect()
+    }
+
+    /// Outputs the edges of the Voronoi cell in gnuplot format.
+    ///
+    /// * `xyz`: a displacement vector to be added to the cell's position.
+    /// * `w`: the writer to output to.
+    fn draw_gnuplot(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()>;
+
+    /// Outputs the edges of the Voronoi cell in POV-Ray format, as a
+    /// collection of spheres and cylinders.
+    ///
+    /// * `xyz`: a displacement vector to be added to the cell's position.
+    /// * `w`: the writer to output to.
+    fn draw_pov(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()>;
+
+    /// Outputs the Voronoi cell in the POV-Ray mesh2 format, describing
+    /// its vertices and the triangles of its faces.
+    ///
+    /// * `xyz`: a displacement vector to be added to the cell's position.
+    /// * `w`: the writer to output to.
+    fn draw_pov_mesh(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()>;
+
+    /// Formats the cell with a voro++ custom output format, without a
+    /// trailing newline.
+    ///
+    /// * `format`: the parsed format.
+    /// * `id`: the ID of the particle.
+    /// * `xyz`: the position of the particle.
+    /// * `r`: the radius of the particle.
+    fn format_custom(
+        &mut self,
+        format: &CustomFormat,
+        id: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> String {
+        format.format(self, id, xyz, r)
+    }
+
+    /// Computes the cross section of the cell by a plane, using
+    /// `plane_intersects` to return early when the plane misses the cell.
+    ///
+    /// * `plane`: the plane in the local coordinate system of the cell.
+    ///
+    /// Return `None` if the plane does not cross the cell.
+    fn slice(&mut self, plane: Plane) -> Option<Polygon> {
+        slice_cell(self, plane)
+    }
+
+    /// Computes the intersection of the cell with another convex cell in
+    /// the same coordinate system, by cutting a copy of the cell with the
+    /// plane of every face of `other`. With neighbor tracking, the new
+    /// faces take the neighbor IDs of the faces of `other`, or the ID -99,
+    /// the default ID of the walls, if `other` does not track neighbors.
+    ///
+    /// * `other`: the cell to intersect with.
+    ///
+    /// Return `None` if the cells do not overlap.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_intersect`.
+    fn intersect<C: VoroCell>(
+        &mut self,
+        other: &mut C,
+    ) -> Option<Self>
+    where
+        Self: Clone + Sized,
+    {
+        self.try_intersect(other)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `intersect`.
+    ///
+    /// Return an error if voro++ reports a fatal error while cutting the
+    /// copy of the cell.
+    fn try_intersect<C: VoroCell>(
+        &mut self,
+        other: &mut C,
+    ) -> Result<Option<Self>, VoroError>
+    where
+        Self: Clone + Sized,
+    {
+        let vertices = other.vertices();
+        let mut cell = self.clone();
+        for face in other.faces() {
+            let n = face.normal;
+            if n == [0.0, 0.0, 0.0] {
+                continue;
+            }
+            let v = vertices[face.vertices[0]];
+            let a = n[0] * v[0] + n[1] * v[1] + n[2] * v[2];
+            let p_id = face.neighbor.unwrap_or(-99);
+            if !cell.try_nplane_rsq(n, 2.0 * a, p_id)? {
+                return Ok(None);
+            }
+        }
+        Ok(Some(cell))
+    }
+
+    /// Computes the volume of the intersection of the cell with another
+    /// convex cell, see `intersect`.
+    ///
+    /// * `other`: the cell to intersect with.
+    ///
+    /// Panics if voro++ reports a fatal error, see
+    /// `try_intersection_volume`.
+    fn intersection_volume<C: VoroCell>(
+        &mut self,
+        other: &mut C,
+    ) -> f64
+    where
+        Self: Clone + Sized,
+    {
+        self.try_intersection_volume(other)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `intersection_volume`.
+    ///
+    /// Return an error if voro++ reports a fatal error, see
+    /// `try_intersect`.
+    fn try_intersection_volume<C: VoroCell>(
+        &mut self,
+        other: &mut C,
+    ) -> Result<f64, VoroError>
+    where
+        Self: Clone + Sized,
+    {
+        Ok(self
+            .try_intersect(other)?
+            .map_or(0.0, |mut c| c.volume()))
+    }
+
     /// This routine tests to see whether the cell intersects a plane by starting
     /// from the guess point up. If up intersects, then it immediately returns true.
     /// Otherwise, it calls the plane_intersects_track() routine.
@@ -635,12 +1281,29 @@ pub trait VoroCell {
     ///
     /// Return false if the plane cut deleted the cell entirely,
     /// true otherwise.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_nplane_rsq`.
     fn nplane_rsq(
         &mut self,
         xyz: DVec3,
         rsq: f64,
         p_id: i32,
-    ) -> bool;
+    ) -> bool {
+        self.try_nplane_rsq(xyz, rsq, p_id)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `nplane_rsq`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// the cell becomes broken by the cut or exceeds the vertex memory
+    /// limit.
+    fn try_nplane_rsq(
+        &mut self,
+        xyz: DVec3,
+        rsq: f64,
+        p_id: i32,
+    ) -> Result<bool, VoroError>;
 
     /// This routine calculates the modulus squared of the vector
     /// before passing it to the main nplane() routine with full
@@ -651,7 +1314,19 @@ pub trait VoroCell {
     ///
     /// Return false if the plane cut deleted the cell entirely,
     /// true otherwise.
-    fn nplane(&mut self, xyz: DVec3, p_id: i32) -> bool;
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_nplane`.
+    fn nplane(&mut self, xyz: DVec3, p_id: i32) -> bool {
+        self.try_nplane(xyz, p_id)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `nplane`.
+    fn try_nplane(
+        &mut self,
+        xyz: DVec3,
+        p_id: i32,
+    ) -> Result<bool, VoroError>;
 
     /// This version of the plane routine just makes up the plane
     /// ID to be zero. It will only be referenced if neighbor
@@ -684,6 +1359,63 @@ impl VoroCell for VoroCellSgl {
             .translate(xyz[0], xyz[1], xyz[2]);
     }
 
+    fn draw_gnuplot(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_gnuplot_0(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn draw_pov(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_pov_0(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn draw_pov_mesh(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_pov_mesh_0(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn try_transform(
+        &mut self,
+        m: DMat3,
+    ) -> Result<(), VoroError> {
+        let m = check_transform(m)?;
+        ffi::transform_cell(self.inner.pin_mut(), &m);
+        Ok(())
+    }
+
     fn volume(&mut self) -> f64 {
         self.inner.pin_mut().volume()
     }
@@ -799,6 +1531,12 @@ impl VoroCell for VoroCellSgl {
         v.into_iter().copied().collect()
     }
 
+    fn vertex_neighbors(&mut self) -> Vec<Vec<usize>> {
+        let mut v = CxxVector::new();
+        ffi::vertex_neighbors(&self.inner, v.pin_mut());
+        decode_lists(v.as_slice())
+    }
+
     fn face_perimeters(&mut self) -> Vec<f64> {
         let mut v = CxxVector::new();
         self.inner.pin_mut().face_perimeters(v.pin_mut());
@@ -811,6 +1549,10 @@ impl VoroCell for VoroCellSgl {
         v.into_iter().copied().collect()
     }
 
+    fn face_neighbors(&mut self) -> Option<Vec<i32>> {
+        None
+    }
+
     fn plane_intersects(
         &mut self,
         xyz: DVec3,
@@ -831,31 +1573,44 @@ impl VoroCell for VoroCellSgl {
         )
     }
 
-    fn nplane_rsq(
+    fn try_nplane_rsq(
         &mut self,
         xyz: DVec3,
         rsq: f64,
         p_id: i32,
-    ) -> bool {
-        self.inner
-            .pin_mut()
-            .nplane_rsq(xyz[0], xyz[1], xyz[2], rsq, p_id)
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().nplane_rsq(
+            xyz[0], xyz[1], xyz[2], rsq, p_id,
+        )?)
     }
 
-    fn nplane(&mut self, xyz: DVec3, p_id: i32) -> bool {
-        self.inner
+    fn try_nplane(
+        &mut self,
+        xyz: DVec3,
+        p_id: i32,
+    ) -> Result<bool, VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .nplane(xyz[0], xyz[1], xyz[2], p_id)
+            .nplane(xyz[0], xyz[1], xyz[2], p_id)?)
     }
 
     fn plane_rsq(&mut self, xyz: DVec3, rsq: f64) -> bool {
         self.inner
             .pin_mut()
             .plane_rsq(xyz[0], xyz[1], xyz[2], rsq)
+            .unwrap_or_else(|e| {
+                panic!("{}", VoroError::from(e))
+            })
     }
 
     fn plane(&mut self, xyz: DVec3) -> bool {
-        self.inner.pin_mut().plane(xyz[0], xyz[1], xyz[2])
+        self.inner
+            .pin_mut()
+            .plane(xyz[0], xyz[1], xyz[2])
+            .unwrap_or_else(|e| {
+                panic!("{}", VoroError::from(e))
+            })
     }
 }
 
@@ -866,6 +1621,66 @@ impl VoroCell for VoroCellNbr {
             .translate(xyz[0], xyz[1], xyz[2]);
     }
 
+    fn draw_gnuplot(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_gnuplot_1(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn draw_pov(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_pov_1(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn draw_pov_mesh(
+        &mut self,
+        xyz: DVec3,
+        w: &mut dyn Write,
+    ) -> io::Result<()> {
+        let c = self.inner.pin_mut();
+        write_output(
+            |out| {
+                draw::cell_draw_pov_mesh_1(
+                    c, xyz[0], xyz[1], xyz[2], out,
+                )
+            },
+            w,
+        )
+    }
+
+    fn try_transform(
+        &mut self,
+        m: DMat3,
+    ) -> Result<(), VoroError> {
+        let m = check_transform(m)?;
+        ffi::transform_cell_neighbor(
+            self.inner.pin_mut(),
+            &m,
+        );
+        Ok(())
+    }
+
     fn volume(&mut self) -> f64 {
         self.inner.pin_mut().volume()
     }
@@ -981,6 +1796,15 @@ impl VoroCell for VoroCellNbr {
         v.into_iter().copied().collect()
     }
 
+    fn vertex_neighbors(&mut self) -> Vec<Vec<usize>> {
+        let mut v = CxxVector::new();
+        ffi::vertex_neighbors_neighbor(
+            &self.inner,
+            v.pin_mut(),
+        );
+        decode_lists(v.as_slice())
+    }
+
     fn face_perimeters(&mut self) -> Vec<f64> {
         let mut v = CxxVector::new();
         self.inner.pin_mut().face_perimeters(v.pin_mut());
@@ -993,6 +1817,10 @@ impl VoroCell for VoroCellNbr {
         v.into_iter().copied().collect()
     }
 
+    fn face_neighbors(&mut self) -> Option<Vec<i32>> {
+        Some(self.neighbors())
+    }
+
     fn plane_intersects(
         &mut self,
         xyz: DVec3,
@@ -1013,31 +1841,44 @@ impl VoroCell for VoroCellNbr {
         )
     }
 
-    fn nplane_rsq(
+    fn try_nplane_rsq(
         &mut self,
         xyz: DVec3,
         rsq: f64,
         p_id: i32,
-    ) -> bool {
-        self.inner
-            .pin_mut()
-            .nplane_rsq(xyz[0], xyz[1], xyz[2], rsq, p_id)
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().nplane_rsq(
+            xyz[0], xyz[1], xyz[2], rsq, p_id,
+        )?)
     }
 
-    fn nplane(&mut self, xyz: DVec3, p_id: i32) -> bool {
-        self.inner
+    fn try_nplane(
+        &mut self,
+        xyz: DVec3,
+        p_id: i32,
+    ) -> Result<bool, VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .nplane(xyz[0], xyz[1], xyz[2], p_id)
+            .nplane(xyz[0], xyz[1], xyz[2], p_id)?)
     }
 
     fn plane_rsq(&mut self, xyz: DVec3, rsq: f64) -> bool {
         self.inner
             .pin_mut()
             .plane_rsq(xyz[0], xyz[1], xyz[2], rsq)
+            .unwrap_or_else(|e| {
+                panic!("{}", VoroError::from(e))
+            })
     }
 
     fn plane(&mut self, xyz: DVec3) -> bool {
-        self.inner.pin_mut().plane(xyz[0], xyz[1], xyz[2])
+        self.inner
+            .pin_mut()
+            .plane(xyz[0], xyz[1], xyz[2])
+            .unwrap_or_else(|e| {
+                panic!("{}", VoroError::from(e))
+            })
     }
 }
 
@@ -1070,6 +1911,25 @@ mod tests {
         assert_eq!(c0.centroid(), [1.0, 1.5, 1.5]);
     }
 
+    #[test]
+    fn invalid_argument_test() {
+        let c0 = VoroCellSgl::try_new(
+            [0.0, 1.0, 1.0],
+            [2.0, 0.0, 2.0],
+        );
+        assert!(c0.is_err());
+        let c1 = VoroCellNbr::try_new(
+            [0.0, 0.0, f64::NAN],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(c1.is_err());
+        let c2 = VoroCellNbr::try_new(
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(c2.is_ok());
+    }
+
     #[test]
     fn mass() {
         let mut c0 = VoroCellSgl::new(
@@ -1124,4 +1984,299 @@ mod tests {
         c0.nplane_rsq([xd, yd, zd], dq, -99);
         assert_eq!(c0.volume(), 4.0);
     }
+
+    #[test]
+    fn faces_test() {
+        let mut c0 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 2.0, 3.0],
+        );
+        let vertices = c0.vertices();
+        assert_eq!(vertices.len(), 8);
+        let faces = c0.faces();
+        assert_eq!(faces.len(), 6);
+        for face in &faces {
+            assert_eq!(face.vertices.len(), 4);
+            assert_eq!(face.neighbor, None);
+            let n = face.normal;
+            let d: Vec<f64> = face
+                .vertices
+                .iter()
+                .map(|&v| {
+                    let p = vertices[v];
+                    n[0] * p[0] + n[1] * p[1] + n[2] * p[2]
+                })
+                .collect();
+            assert!(d
+                .iter()
+                .all(|x| (x - d[0]).abs() < 1e-10));
+        }
+        let area: f64 = faces.iter().map(|f| f.area).sum();
+        assert!((area - 22.0).abs() < 1e-10);
+        let perimeter: f64 =
+            faces.iter().map(|f| f.perimeter).sum();
+        assert!((perimeter - 48.0).abs() < 1e-10);
+
+        let mut c1 = VoroCellNbr::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        let neighbors = c1.neighbors();
+        let faces = c1.faces();
+        for (face, n) in faces.iter().zip(neighbors) {
+            assert_eq!(face.neighbor, Some(n));
+        }
+    }
+
+    #[test]
+    fn edges_test() {
+        let mut c0 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 2.0, 3.0],
+        );
+        let adjacency = c0.vertex_neighbors();
+        assert_eq!(adjacency.len(), 8);
+        assert!(adjacency.iter().all(|n| n.len() == 3));
+        let edges = c0.edges();
+        assert_eq!(edges.len(), 12);
+        assert!(edges.iter().all(|&(i, j)| i < j));
+        for &(i, j) in &edges {
+            assert!(adjacency[j].contains(&i));
+        }
+        let length: f64 = c0.edge_lengths().iter().sum();
+        assert!((length - 24.0).abs() < 1e-10);
+        assert!(
+            (length - c0.total_edge_distance()).abs()
+                < 1e-10
+        );
+
+        let mut c1 = VoroCellNbr::new_octahedron(1.0);
+        assert_eq!(c1.edges().len(), 12);
+    }
+
+    #[test]
+    fn polyhedron_test() {
+        let mut c0 = VoroCellSgl::from_half_spaces(&[
+            ([1.0, 0.0, 0.0], 1.0),
+            ([-1.0, 0.0, 0.0], 1.0),
+            ([0.0, 2.0, 0.0], 2.0),
+            ([0.0, -1.0, 0.0], 1.0),
+            ([0.0, 0.0, 1.0], 0.5),
+            ([0.0, 0.0, -1.0], 0.5),
+        ]);
+        assert!((c0.volume() - 4.0).abs() < 1e-10);
+
+        let mut c1 = VoroCellNbr::from_half_spaces(&[
+            ([1.0, 1.0, 1.0], 1.0),
+            ([-1.0, 0.0, 0.0], 0.0),
+            ([0.0, -1.0, 0.0], 0.0),
+            ([0.0, 0.0, -1.0], 0.0),
+        ]);
+        assert!((c1.volume() - 1.0 / 6.0).abs() < 1e-10);
+        let mut ids = c1.neighbors();
+        ids.sort();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+
+        let open = VoroCellSgl::try_from_half_spaces(&[
+            ([1.0, 0.0, 0.0], 1.0),
+            ([0.0, 1.0, 0.0], 1.0),
+        ]);
+        assert!(open.is_err());
+        let empty = VoroCellSgl::try_from_half_spaces(&[
+            ([1.0, 0.0, 0.0], -1.0),
+            ([-1.0, 0.0, 0.0], -1.0),
+        ]);
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn hull_test() {
+        let mut points = Vec::new();
+        for i in 0..8 {
+            points.push([
+                (i & 1) as f64 + 2.0,
+                ((i >> 1) & 1) as f64 * 2.0,
+                ((i >> 2) & 1) as f64 * 3.0,
+            ]);
+        }
+        points.push([2.5, 1.0, 1.5]);
+        let mut c0 = VoroCellSgl::from_points(&points);
+        assert!((c0.volume() - 6.0).abs() < 1e-8);
+        assert_eq!(c0.number_of_faces(), 6);
+
+        let mut c1 = VoroCellNbr::from_points(&[
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+        assert!((c1.volume() - 1.0 / 6.0).abs() < 1e-8);
+
+        // an interior point just off the mean of the points
+        let mut points = Vec::new();
+        for i in 0..8 {
+            points.push([
+                (i & 1) as f64 * 2.0 - 1.0,
+                ((i >> 1) & 1) as f64 * 2.0 - 1.0,
+                ((i >> 2) & 1) as f64 * 2.0 - 1.0,
+            ]);
+        }
+        points.push([1e-9, 0.0, 0.0]);
+        let mut c2 = VoroCellSgl::from_points(&points);
+        assert!((c2.volume() - 8.0).abs() < 1e-8);
+        assert_eq!(c2.number_of_faces(), 6);
+        for v in c2.vertices() {
+            assert!(v
+                .iter()
+                .all(|x| (x.abs() - 1.0).abs() < 1e-8));
+        }
+
+        let flat = VoroCellSgl::try_from_points(&[
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ]);
+        assert!(flat.is_err());
+    }
+
+    #[test]
+    fn intersect_test() {
+        let mut c0 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [2.0, 2.0, 2.0],
+        );
+        let mut c1 = VoroCellNbr::new(
+            [1.0, 1.0, -1.0],
+            [3.0, 4.0, 1.0],
+        );
+        let mut c2 = c0.intersect(&mut c1).unwrap();
+        assert!((c2.volume() - 1.0).abs() < 1e-10);
+        assert!(
+            (c1.intersection_volume(&mut c0) - 1.0).abs()
+                < 1e-10
+        );
+        assert!((c0.volume() - 8.0).abs() < 1e-10);
+
+        let mut c3 = VoroCellSgl::new(
+            [5.0, 5.0, 5.0],
+            [6.0, 6.0, 6.0],
+        );
+        assert!(c0.intersect(&mut c3).is_none());
+        assert_eq!(c0.intersection_volume(&mut c3), 0.0);
+
+        // a cell without neighbor tracking labels the new faces with -99.
+        let mut c4 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let mut c5 =
+            c4.try_intersect(&mut c0).unwrap().unwrap();
+        assert!((c5.volume() - 1.0).abs() < 1e-10);
+        assert!(c5
+            .faces()
+            .iter()
+            .any(|face| face.neighbor == Some(-99)));
+        assert!(
+            (c4.try_intersection_volume(&mut c0).unwrap()
+                - 1.0)
+                .abs()
+                < 1e-10
+        );
+    }
+
+    #[test]
+    fn transform_test() {
+        let mut c0 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let neighbors = c0.neighbors();
+        let edges = c0.edges();
+
+        c0.scale(2.0);
+        assert!((c0.volume() - 64.0).abs() < 1e-10);
+        c0.transform([
+            [1.0, 0.5, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.25],
+        ]);
+        assert!((c0.volume() - 16.0).abs() < 1e-10);
+
+        let h = 0.5f64.sqrt();
+        c0.rotate_quaternion([h, 0.0, 0.0, h]);
+        c0.rotate([
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0],
+            [0.0, 1.0, 0.0],
+        ]);
+        assert!((c0.volume() - 16.0).abs() < 1e-10);
+        assert_eq!(c0.neighbors(), neighbors);
+        assert_eq!(c0.edges(), edges);
+
+        let mut c1 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 2.0, 3.0],
+        );
+        c1.rotate_quaternion([h, 0.0, 0.0, h]);
+        let c = c1.centroid();
+        assert!((c[0] + 1.0).abs() < 1e-10);
+        assert!((c[1] - 0.5).abs() < 1e-10);
+        assert!((c[2] - 1.5).abs() < 1e-10);
+
+        let mirror = [
+            [-1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ];
+        assert!(c1.try_transform(mirror).is_err());
+        assert!(c1
+            .try_rotate([
+                [2.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn send_test() {
+        let c0 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let volumes: Vec<f64> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let c0 = &c0;
+                    s.spawn(move || {
+                        let mut c1 = c0.clone();
+                        c1.plane([
+                            1.0 - 0.25 * i as f64,
+                            0.0,
+                            0.0,
+                        ]);
+                        c1.volume()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .collect()
+        });
+        assert_eq!(volumes[0], 6.0);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut c2 = VoroCellSgl::new(
+                [-1.0, -1.0, -1.0],
+                [1.0, 1.0, 1.0],
+            );
+            c2.plane([1.0, 0.0, 0.0]);
+            tx.send(c2).unwrap();
+        });
+        let mut c2 = rx.recv().unwrap();
+        assert_eq!(c2.volume(), 6.0);
+    }
 }
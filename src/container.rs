@@ -21,6 +21,10 @@ pub mod ffi {
             crate::container_loop::ffi::c_loop_subset;
         type c_loop_order =
             crate::container_loop::ffi::c_loop_order;
+        type c_loop_all_periodic =
+            crate::container_loop::ffi::c_loop_all_periodic;
+        type c_loop_order_periodic =
+            crate::container_loop::ffi::c_loop_order_periodic;
 
         fn container_to_wall_list(
             value: Pin<&mut container>,
@@ -45,17 +49,17 @@ pub mod ffi {
             yperiodic_: bool,
             zperiodic_: bool,
             init_mem: i32,
-        ) -> UniquePtr<container>;
+        ) -> Result<UniquePtr<container>>;
 
         unsafe fn add_wall(
             self: Pin<&mut container>,
             w: Pin<&mut wall>,
-        );
+        ) -> Result<()>;
         #[rust_name = "add_walls"]
         unsafe fn add_wall(
             self: Pin<&mut container>,
             w: Pin<&mut wall_list>,
-        );
+        ) -> Result<()>;
         fn point_inside_walls(
             self: Pin<&mut container>,
             x: f64,
@@ -69,7 +73,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "apply_walls_1"]
         fn apply_walls(
             self: Pin<&mut container>,
@@ -77,7 +81,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         fn point_inside(
             self: Pin<&mut container>,
@@ -96,7 +100,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        );
+        ) -> Result<()>;
         #[rust_name = "put_with_particle_order"]
         fn put(
             self: Pin<&mut container>,
@@ -105,11 +109,13 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        );
-        fn compute_all_cells(self: Pin<&mut container>);
+        ) -> Result<()>;
+        fn compute_all_cells(
+            self: Pin<&mut container>,
+        ) -> Result<()>;
         fn sum_cell_volumes(
             self: Pin<&mut container>,
-        ) -> f64;
+        ) -> Result<f64>;
         fn find_voronoi_cell(
             self: Pin<&mut container>,
             x: f64,
@@ -119,57 +125,57 @@ pub mod ffi {
             ry: &mut f64,
             rz: &mut f64,
             pid: &mut i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_0"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_all>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_1"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_all>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_2"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_subset>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_3"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_subset>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_4"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_order>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_5"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_order>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_with_index_0"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell>,
             ijk: i32,
             q: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_with_index_1"]
         fn compute_cell(
             self: Pin<&mut container>,
             c: Pin<&mut voronoicell_neighbor>,
             ijk: i32,
             q: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_ghost_0"]
         fn compute_ghost_cell(
             self: Pin<&mut container>,
@@ -177,7 +183,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_ghost_1"]
         fn compute_ghost_cell(
             self: Pin<&mut container>,
@@ -185,7 +191,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         type container_poly;
         #[rust_name = "new_container_poly"]
@@ -203,17 +209,17 @@ pub mod ffi {
             yperiodic_: bool,
             zperiodic_: bool,
             init_mem: i32,
-        ) -> UniquePtr<container_poly>;
+        ) -> Result<UniquePtr<container_poly>>;
 
         unsafe fn add_wall(
             self: Pin<&mut container_poly>,
             w: Pin<&mut wall>,
-        );
+        ) -> Result<()>;
         #[rust_name = "add_walls"]
         unsafe fn add_wall(
             self: Pin<&mut container_poly>,
             w: Pin<&mut wall_list>,
-        );
+        ) -> Result<()>;
         fn point_inside_walls(
             self: Pin<&mut container_poly>,
             x: f64,
@@ -227,7 +233,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "apply_walls_1"]
         fn apply_walls(
             self: Pin<&mut container_poly>,
@@ -235,7 +241,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         fn point_inside(
             self: Pin<&mut container_poly>,
@@ -255,7 +261,7 @@ pub mod ffi {
             y: f64,
             z: f64,
             r: f64,
-        );
+        ) -> Result<()>;
         #[rust_name = "put_with_particle_order"]
         fn put(
             self: Pin<&mut container_poly>,
@@ -265,13 +271,13 @@ pub mod ffi {
             y: f64,
             z: f64,
             r: f64,
-        );
+        ) -> Result<()>;
         fn compute_all_cells(
             self: Pin<&mut container_poly>,
-        );
+        ) -> Result<()>;
         fn sum_cell_volumes(
             self: Pin<&mut container_poly>,
-        ) -> f64;
+        ) -> Result<f64>;
         fn find_voronoi_cell(
             self: Pin<&mut container_poly>,
             x: f64,
@@ -281,57 +287,57 @@ pub mod ffi {
             ry: &mut f64,
             rz: &mut f64,
             pid: &mut i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_0"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_all>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_1"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_all>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_2"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_subset>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_3"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_subset>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_4"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell>,
             vl: Pin<&mut c_loop_order>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_5"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell_neighbor>,
             vl: Pin<&mut c_loop_order>,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_with_index_0"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell>,
             ijk: i32,
             q: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_cell_with_index_1"]
         fn compute_cell(
             self: Pin<&mut container_poly>,
             c: Pin<&mut voronoicell_neighbor>,
             ijk: i32,
             q: i32,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_ghost_0"]
         fn compute_ghost_cell(
             self: Pin<&mut container_poly>,
@@ -340,7 +346,7 @@ pub mod ffi {
             y: f64,
             z: f64,
             r: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "compute_ghost_1"]
         fn compute_ghost_cell(
             self: Pin<&mut container_poly>,
@@ -349,31 +355,233 @@ pub mod ffi {
             y: f64,
             z: f64,
             r: f64,
-        ) -> bool;
+        ) -> Result<bool>;
+
+        type container_periodic;
+        #[rust_name = "new_container_periodic"]
+        fn construct(
+            bx_: f64,
+            bxy_: f64,
+            by_: f64,
+            bxz_: f64,
+            byz_: f64,
+            bz_: f64,
+            nx_: i32,
+            ny_: i32,
+            nz_: i32,
+            init_mem_: i32,
+        ) -> Result<UniquePtr<container_periodic>>;
+
+        fn total_particles(
+            self: Pin<&mut container_periodic>,
+        ) -> i32;
+
+        fn clear(self: Pin<&mut container_periodic>);
+        fn put(
+            self: Pin<&mut container_periodic>,
+            n: i32,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<()>;
+        #[rust_name = "put_with_particle_order"]
+        fn put(
+            self: Pin<&mut container_periodic>,
+            vo: Pin<&mut particle_order>,
+            n: i32,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<()>;
+        fn sum_cell_volumes(
+            self: Pin<&mut container_periodic>,
+        ) -> Result<f64>;
+        fn find_voronoi_cell(
+            self: Pin<&mut container_periodic>,
+            x: f64,
+            y: f64,
+            z: f64,
+            rx: &mut f64,
+            ry: &mut f64,
+            rz: &mut f64,
+            pid: &mut i32,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_0"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell>,
+            vl: Pin<&mut c_loop_all_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_1"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell_neighbor>,
+            vl: Pin<&mut c_loop_all_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_2"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell>,
+            vl: Pin<&mut c_loop_order_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_3"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell_neighbor>,
+            vl: Pin<&mut c_loop_order_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_ghost_0"]
+        fn compute_ghost_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "compute_ghost_1"]
+        fn compute_ghost_cell(
+            self: Pin<&mut container_periodic>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+
+        type container_periodic_poly;
+        #[rust_name = "new_container_periodic_poly"]
+        fn construct(
+            bx_: f64,
+            bxy_: f64,
+            by_: f64,
+            bxz_: f64,
+            byz_: f64,
+            bz_: f64,
+            nx_: i32,
+            ny_: i32,
+            nz_: i32,
+            init_mem_: i32,
+        ) -> Result<UniquePtr<container_periodic_poly>>;
+
+        fn total_particles(
+            self: Pin<&mut container_periodic_poly>,
+        ) -> i32;
+
+        fn clear(self: Pin<&mut container_periodic_poly>);
+        fn put(
+            self: Pin<&mut container_periodic_poly>,
+            n: i32,
+            x: f64,
+            y: f64,
+            z: f64,
+            r: f64,
+        ) -> Result<()>;
+        #[rust_name = "put_with_particle_order"]
+        fn put(
+            self: Pin<&mut container_periodic_poly>,
+            vo: Pin<&mut particle_order>,
+            n: i32,
+            x: f64,
+            y: f64,
+            z: f64,
+            r: f64,
+        ) -> Result<()>;
+        fn sum_cell_volumes(
+            self: Pin<&mut container_periodic_poly>,
+        ) -> Result<f64>;
+        fn find_voronoi_cell(
+            self: Pin<&mut container_periodic_poly>,
+            x: f64,
+            y: f64,
+            z: f64,
+            rx: &mut f64,
+            ry: &mut f64,
+            rz: &mut f64,
+            pid: &mut i32,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_0"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell>,
+            vl: Pin<&mut c_loop_all_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_1"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell_neighbor>,
+            vl: Pin<&mut c_loop_all_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_2"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell>,
+            vl: Pin<&mut c_loop_order_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_cell_3"]
+        fn compute_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell_neighbor>,
+            vl: Pin<&mut c_loop_order_periodic>,
+        ) -> Result<bool>;
+        #[rust_name = "compute_ghost_0"]
+        fn compute_ghost_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+            r: f64,
+        ) -> Result<bool>;
+        #[rust_name = "compute_ghost_1"]
+        fn compute_ghost_cell(
+            self: Pin<&mut container_periodic_poly>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+            r: f64,
+        ) -> Result<bool>;
     }
 }
 
 use crate::cell::{VoroCellNbr, VoroCellSgl};
 use crate::container_loop::{
-    ContainerLoop, LoopAll, LoopMarked, LoopSubset,
+    ContainerLoop, LoopAll, LoopAllPeriodic, LoopMarked,
+    LoopMarkedPeriodic, LoopSubset,
+};
+use crate::custom_format::CustomFormat;
+use crate::error::{
+    check_box, check_grids, check_memory, check_unit_cell,
+    VoroError, VoroErrorKind,
 };
+use crate::import::ContainerImport;
+use crate::neighbor_image::Domain;
 use crate::particle_marker::ParticleMarker;
 use crate::prelude::VoroCell;
+use crate::slice::{Plane, Slice};
+use crate::tessellation::Tessellation;
 use crate::wall::ffi::{
-    wall_cone_to_wall, wall_cylinder_to_wall,
-    wall_plane_to_wall, wall_sphere_to_wall,
+    wall_box_to_wall, wall_cone_to_wall,
+    wall_custom_to_wall, wall_cylinder_to_wall,
+    wall_ellipsoid_to_wall, wall_plane_to_wall,
+    wall_polyhedron_to_wall, wall_sphere_to_wall,
+    wall_torus_to_wall,
 };
+use crate::wall::sealed::WallBase;
 use crate::wall::{
-    WallCone, WallCylinder, WallPlane, WallSphere,
+    Wall, WallBox, WallCone, WallCustom, WallCylinder,
+    WallEllipsoid, WallPlane, WallPolyhedron, WallSphere,
+    WallTorus,
 };
 use crate::wall_list::{
     WallList, Walls, Walls0, Walls1, Walls2, Walls3,
 };
-use cxx::UniquePtr;
+use cxx::{memory::UniquePtrTarget, UniquePtr};
 use ffi::{
     container_poly_to_wall_list, container_to_wall_list,
 };
+use std::io::{self, Write};
 use std::marker::PhantomData;
+use std::pin::Pin;
 
 type DVec3 = [f64; 3];
 type IVec3 = [i32; 3];
@@ -393,9 +601,22 @@ type BVec3 = [bool; 3];
 /// this struct.
 pub struct ContainerStd<'a> {
     pub(crate) inner: UniquePtr<ffi::container>,
+    pub(crate) domain: Domain,
+    // declared after `inner`, so that the walls are dropped after the
+    // container referring to them.
+    owned_walls: Vec<Box<dyn Wall + Send>>,
+    // whether a `WallCustom` was added, whose callbacks may not be
+    // called from several threads at once.
+    pub(crate) has_custom_walls: bool,
     phantom: PhantomData<&'a ()>,
 }
 
+// SAFETY: the container owns its particles, and it only refers to walls
+// that are `Send`, either borrowed for `'a` or owned by this struct. It
+// is not `Sync`, since computing a cell through `&self` updates the
+// search state of the container.
+unsafe impl Send for ContainerStd<'_> {}
+
 impl<'a> ContainerStd<'a> {
     /// The class constructor sets up the geometry of container.
     ///
@@ -420,6 +641,22 @@ impl<'a> ContainerStd<'a> {
         )
     }
 
+    /// Fallible version of `new`.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        grids: IVec3,
+        is_periodic: BVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_memory(
+            xyz_min,
+            xyz_max,
+            grids,
+            is_periodic,
+            16,
+        )
+    }
+
     /// The class constructor sets up the geometry of container.
     ///
     /// * `xyz_min`: the minimum coordinates.
@@ -437,7 +674,31 @@ impl<'a> ContainerStd<'a> {
         is_periodic: BVec3,
         initial_memory: i32,
     ) -> Self {
-        Self {
+        Self::try_new_with_memory(
+            xyz_min,
+            xyz_max,
+            grids,
+            is_periodic,
+            initial_memory,
+        )
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_memory`.
+    ///
+    /// Return an error if the arguments are invalid, or if voro++ fails
+    /// to allocate the container.
+    pub fn try_new_with_memory(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        grids: IVec3,
+        is_periodic: BVec3,
+        initial_memory: i32,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
+        check_grids(grids)?;
+        check_memory(initial_memory)?;
+        Ok(Self {
             inner: ffi::new_container(
                 xyz_min[0],
                 xyz_max[0],
@@ -452,9 +713,82 @@ impl<'a> ContainerStd<'a> {
                 is_periodic[1],
                 is_periodic[2],
                 initial_memory,
+            )?,
+            domain: Domain::rectangular(
+                xyz_min,
+                xyz_max,
+                is_periodic,
             ),
+            owned_walls: Vec::new(),
+            has_custom_walls: false,
             phantom: PhantomData,
+        })
+    }
+
+    /// Adds a wall to the container, taking ownership of it.
+    ///
+    /// * `wall`: the wall to add. Unlike `add_wall`, the wall is kept
+    /// alive by this struct, so it does not need to outlive it.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_add_wall_owned`.
+    pub fn add_wall_owned<T>(&mut self, wall: T)
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.try_add_wall_owned(wall)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `add_wall_owned`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// more walls are added than voro++ can store. The wall is dropped in
+    /// that case.
+    pub fn try_add_wall_owned<T>(
+        &mut self,
+        wall: T,
+    ) -> Result<(), VoroError>
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.owned_walls.push(Box::new(wall));
+        let wall = self.owned_walls.last_mut().unwrap();
+        let wall = wall.as_mut() as *mut (dyn Wall + Send)
+            as *mut T;
+        // SAFETY: the wall is heap-allocated and owned by `owned_walls`,
+        // which is dropped after `inner`, and no reference to it is kept.
+        let result = unsafe { self.try_add_wall_ptr(wall) };
+        if result.is_err() {
+            self.owned_walls.pop();
         }
+        result
+    }
+
+    /// Adds the C++ object of a wall to the container.
+    ///
+    /// # Safety
+    ///
+    /// `wall` must be valid and not aliased for this call, and must stay
+    /// in place until `inner` is dropped.
+    unsafe fn try_add_wall_ptr<T: WallBase>(
+        &mut self,
+        wall: *mut T,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= T::CUSTOM;
+        let w0 = unsafe { (*wall).base() };
+        unsafe { self.inner.pin_mut().add_wall(w0)? };
+        Ok(())
+    }
+
+    /// Pins the C++ container for a voro++ routine that does not move
+    /// its particles, see `pin_shared`.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn inner_pin(
+        &self,
+    ) -> Pin<&mut ffi::container> {
+        // SAFETY: the container is not `Sync`, and the returned reference
+        // is borrowed from `self`.
+        unsafe { pin_shared(self.inner.as_mut_ptr()) }
     }
 }
 
@@ -472,9 +806,22 @@ impl<'a> ContainerStd<'a> {
 /// this struct.
 pub struct ContainerRad<'a> {
     pub(crate) inner: UniquePtr<ffi::container_poly>,
+    pub(crate) domain: Domain,
+    // declared after `inner`, so that the walls are dropped after the
+    // container referring to them.
+    owned_walls: Vec<Box<dyn Wall + Send>>,
+    // whether a `WallCustom` was added, whose callbacks may not be
+    // called from several threads at once.
+    pub(crate) has_custom_walls: bool,
     phantom: PhantomData<&'a ()>,
 }
 
+// SAFETY: the container owns its particles, and it only refers to walls
+// that are `Send`, either borrowed for `'a` or owned by this struct. It
+// is not `Sync`, since computing a cell through `&self` updates the
+// search state of the container.
+unsafe impl Send for ContainerRad<'_> {}
+
 impl<'a> ContainerRad<'a> {
     /// The class constructor sets up the geometry of container.
     ///
@@ -499,6 +846,22 @@ impl<'a> ContainerRad<'a> {
         )
     }
 
+    /// Fallible version of `new`.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        grids: IVec3,
+        is_periodic: BVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_memory(
+            xyz_min,
+            xyz_max,
+            grids,
+            is_periodic,
+            16,
+        )
+    }
+
     /// The class constructor sets up the geometry of container.
     ///
     /// * `xyz_min`: the minimum coordinates.
@@ -516,7 +879,31 @@ impl<'a> ContainerRad<'a> {
         is_periodic: BVec3,
         initial_memory: i32,
     ) -> Self {
-        Self {
+        Self::try_new_with_memory(
+            xyz_min,
+            xyz_max,
+            grids,
+            is_periodic,
+            initial_memory,
+        )
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_memory`.
+    ///
+    /// Return an error if the arguments are invalid, or if voro++ fails
+    /// to allocate the container.
+    pub fn try_new_with_memory(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        grids: IVec3,
+        is_periodic: BVec3,
+        initial_memory: i32,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
+        check_grids(grids)?;
+        check_memory(initial_memory)?;
+        Ok(Self {
             inner: ffi::new_container_poly(
                 xyz_min[0],
                 xyz_max[0],
@@ -531,192 +918,699 @@ impl<'a> ContainerRad<'a> {
                 is_periodic[1],
                 is_periodic[2],
                 initial_memory,
+            )?,
+            domain: Domain::rectangular(
+                xyz_min,
+                xyz_max,
+                is_periodic,
             ),
+            owned_walls: Vec::new(),
+            has_custom_walls: false,
             phantom: PhantomData,
-        }
+        })
     }
-}
 
-impl<'a> Walls0 for ContainerStd<'a> {
-    fn point_inside_walls(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside_walls(xyz[0], xyz[1], xyz[2])
+    /// Adds a wall to the container, taking ownership of it.
+    ///
+    /// * `wall`: the wall to add. Unlike `add_wall`, the wall is kept
+    /// alive by this struct, so it does not need to outlive it.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_add_wall_owned`.
+    pub fn add_wall_owned<T>(&mut self, wall: T)
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.try_add_wall_owned(wall)
+            .unwrap_or_else(|e| panic!("{e}"))
     }
-}
 
-impl<'a> Walls0 for ContainerRad<'a> {
-    fn point_inside_walls(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside_walls(xyz[0], xyz[1], xyz[2])
+    /// Fallible version of `add_wall_owned`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// more walls are added than voro++ can store. The wall is dropped in
+    /// that case.
+    pub fn try_add_wall_owned<T>(
+        &mut self,
+        wall: T,
+    ) -> Result<(), VoroError>
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.owned_walls.push(Box::new(wall));
+        let wall = self.owned_walls.last_mut().unwrap();
+        let wall = wall.as_mut() as *mut (dyn Wall + Send)
+            as *mut T;
+        // SAFETY: the wall is heap-allocated and owned by `owned_walls`,
+        // which is dropped after `inner`, and no reference to it is kept.
+        let result = unsafe { self.try_add_wall_ptr(wall) };
+        if result.is_err() {
+            self.owned_walls.pop();
+        }
+        result
     }
-}
 
-impl<'a> Walls1<VoroCellSgl> for ContainerStd<'a> {
-    fn apply_walls(
+    /// Adds the C++ object of a wall to the container.
+    ///
+    /// # Safety
+    ///
+    /// `wall` must be valid and not aliased for this call, and must stay
+    /// in place until `inner` is dropped.
+    unsafe fn try_add_wall_ptr<T: WallBase>(
         &mut self,
-        cell: &mut VoroCellSgl,
-        xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_0(
-            cell.inner.pin_mut(),
-            xyz[0],
-            xyz[1],
-            xyz[2],
-        )
+        wall: *mut T,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= T::CUSTOM;
+        let w0 = unsafe { (*wall).base() };
+        unsafe { self.inner.pin_mut().add_wall(w0)? };
+        Ok(())
+    }
+
+    /// Pins the C++ container for a voro++ routine that does not move
+    /// its particles, see `pin_shared`.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn inner_pin(
+        &self,
+    ) -> Pin<&mut ffi::container_poly> {
+        // SAFETY: the container is not `Sync`, and the returned reference
+        // is borrowed from `self`.
+        unsafe { pin_shared(self.inner.as_mut_ptr()) }
+    }
+}
+
+/// A class for computing regular Voronoi tessellations in a triclinic
+/// periodic domain.
+///
+/// The domain is periodic in all three directions and is spanned by the
+/// unit cell vectors (bx, 0, 0), (bxy, by, 0) and (bxz, byz, bz). Particles
+/// put into the container are remapped into the primary domain, so the
+/// container never deletes a particle for being out of bounds.
+///
+/// This class does not implement `Clone` trait because there is no
+/// well-defined copy constructor in the original voro++ code.
+///
+/// Unlike `ContainerStd`, walls cannot be added to this struct, since
+/// voro++ does not support walls for periodic containers.
+pub struct ContainerPeriodicStd {
+    pub(crate) inner: UniquePtr<ffi::container_periodic>,
+    pub(crate) domain: Domain,
+}
+
+// SAFETY: the container owns its particles. It is not `Sync`, since
+// computing a cell through `&self` updates the search state of the
+// container and creates periodic images.
+unsafe impl Send for ContainerPeriodicStd {}
+
+impl ContainerPeriodicStd {
+    /// The class constructor sets up the geometry of container.
+    ///
+    /// * `box_size`: the diagonal entries of the unit cell, (bx, by, bz).
+    /// * `box_shear`: the off-diagonal entries of the unit cell,
+    /// (bxy, bxz, byz).
+    /// * `grids`: the number of grid blocks in each of the
+    /// three coordinate directions.
+    pub fn new(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+    ) -> Self {
+        Self::new_with_memory(
+            box_size, box_shear, grids, 16,
+        )
+    }
+
+    /// Fallible version of `new`.
+    pub fn try_new(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_memory(
+            box_size, box_shear, grids, 16,
+        )
+    }
+
+    /// The class constructor sets up the geometry of container.
+    ///
+    /// * `box_size`: the diagonal entries of the unit cell, (bx, by, bz).
+    /// * `box_shear`: the off-diagonal entries of the unit cell,
+    /// (bxy, bxz, byz).
+    /// * `grids`: the number of grid blocks in each of the
+    /// three coordinate directions.
+    /// * `initial_memory`: the initial memory allocation for each grid,
+    /// in terms of particle count.
+    pub fn new_with_memory(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+        initial_memory: i32,
+    ) -> Self {
+        Self::try_new_with_memory(
+            box_size,
+            box_shear,
+            grids,
+            initial_memory,
+        )
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_memory`.
+    ///
+    /// Return an error if the arguments are invalid, or if voro++ fails
+    /// to allocate the container.
+    pub fn try_new_with_memory(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+        initial_memory: i32,
+    ) -> Result<Self, VoroError> {
+        check_unit_cell(box_size, box_shear)?;
+        check_grids(grids)?;
+        check_memory(initial_memory)?;
+        Ok(Self {
+            inner: ffi::new_container_periodic(
+                box_size[0],
+                box_shear[0],
+                box_size[1],
+                box_shear[1],
+                box_shear[2],
+                box_size[2],
+                grids[0],
+                grids[1],
+                grids[2],
+                initial_memory,
+            )?,
+            domain: Domain::triclinic(box_size, box_shear),
+        })
+    }
+
+    /// Pins the C++ container for a voro++ routine that does not move
+    /// its particles, see `pin_shared`.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn inner_pin(
+        &self,
+    ) -> Pin<&mut ffi::container_periodic> {
+        // SAFETY: the container is not `Sync`, and the returned reference
+        // is borrowed from `self`.
+        unsafe { pin_shared(self.inner.as_mut_ptr()) }
+    }
+}
+
+/// A class for computing radical Voronoi tessellations in a triclinic
+/// periodic domain.
+///
+/// The domain is periodic in all three directions and is spanned by the
+/// unit cell vectors (bx, 0, 0), (bxy, by, 0) and (bxz, byz, bz). Particles
+/// put into the container are remapped into the primary domain, so the
+/// container never deletes a particle for being out of bounds.
+///
+/// This class does not implement `Clone` trait because there is no
+/// well-defined copy constructor in the original voro++ code.
+///
+/// Unlike `ContainerRad`, walls cannot be added to this struct, since
+/// voro++ does not support walls for periodic containers.
+pub struct ContainerPeriodicRad {
+    pub(crate) inner:
+        UniquePtr<ffi::container_periodic_poly>,
+    pub(crate) domain: Domain,
+}
+
+// SAFETY: the container owns its particles. It is not `Sync`, since
+// computing a cell through `&self` updates the search state of the
+// container and creates periodic images.
+unsafe impl Send for ContainerPeriodicRad {}
+
+impl ContainerPeriodicRad {
+    /// The class constructor sets up the geometry of container.
+    ///
+    /// * `box_size`: the diagonal entries of the unit cell, (bx, by, bz).
+    /// * `box_shear`: the off-diagonal entries of the unit cell,
+    /// (bxy, bxz, byz).
+    /// * `grids`: the number of grid blocks in each of the
+    /// three coordinate directions.
+    pub fn new(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+    ) -> Self {
+        Self::new_with_memory(
+            box_size, box_shear, grids, 16,
+        )
+    }
+
+    /// Fallible version of `new`.
+    pub fn try_new(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_memory(
+            box_size, box_shear, grids, 16,
+        )
+    }
+
+    /// The class constructor sets up the geometry of container.
+    ///
+    /// * `box_size`: the diagonal entries of the unit cell, (bx, by, bz).
+    /// * `box_shear`: the off-diagonal entries of the unit cell,
+    /// (bxy, bxz, byz).
+    /// * `grids`: the number of grid blocks in each of the
+    /// three coordinate directions.
+    /// * `initial_memory`: the initial memory allocation for each grid,
+    /// in terms of particle count.
+    pub fn new_with_memory(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+        initial_memory: i32,
+    ) -> Self {
+        Self::try_new_with_memory(
+            box_size,
+            box_shear,
+            grids,
+            initial_memory,
+        )
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_memory`.
+    ///
+    /// Return an error if the arguments are invalid, or if voro++ fails
+    /// to allocate the container.
+    pub fn try_new_with_memory(
+        box_size: DVec3,
+        box_shear: DVec3,
+        grids: IVec3,
+        initial_memory: i32,
+    ) -> Result<Self, VoroError> {
+        check_unit_cell(box_size, box_shear)?;
+        check_grids(grids)?;
+        check_memory(initial_memory)?;
+        Ok(Self {
+            inner: ffi::new_container_periodic_poly(
+                box_size[0],
+                box_shear[0],
+                box_size[1],
+                box_shear[1],
+                box_shear[2],
+                box_size[2],
+                grids[0],
+                grids[1],
+                grids[2],
+                initial_memory,
+            )?,
+            domain: Domain::triclinic(box_size, box_shear),
+        })
+    }
+
+    /// Pins the C++ container for a voro++ routine that does not move
+    /// its particles, see `pin_shared`.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn inner_pin(
+        &self,
+    ) -> Pin<&mut ffi::container_periodic_poly> {
+        // SAFETY: the container is not `Sync`, and the returned reference
+        // is borrowed from `self`.
+        unsafe { pin_shared(self.inner.as_mut_ptr()) }
+    }
+}
+
+impl<'a> Walls0 for ContainerStd<'a> {
+    fn point_inside_walls(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside_walls(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl<'a> Walls0 for ContainerRad<'a> {
+    fn point_inside_walls(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside_walls(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl<'a> Walls1<VoroCellSgl> for ContainerStd<'a> {
+    fn try_apply_walls(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
     }
 }
 
 impl<'a> Walls1<VoroCellNbr> for ContainerStd<'a> {
-    fn apply_walls(
+    fn try_apply_walls(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_1(
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
 impl<'a> Walls1<VoroCellSgl> for ContainerRad<'a> {
-    fn apply_walls(
+    fn try_apply_walls(
         &mut self,
         cell: &mut VoroCellSgl,
         xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_0(
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
 impl<'a> Walls1<VoroCellNbr> for ContainerRad<'a> {
-    fn apply_walls(
+    fn try_apply_walls(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_1(
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
 impl<'a> Walls2<'a, WallSphere> for ContainerStd<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallSphere) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallSphere,
+    ) -> Result<(), VoroError> {
         let w0 = wall_sphere_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallPlane> for ContainerStd<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallPlane) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPlane,
+    ) -> Result<(), VoroError> {
         let w0 = wall_plane_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCylinder> for ContainerStd<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCylinder) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCylinder,
+    ) -> Result<(), VoroError> {
         let w0 =
             wall_cylinder_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCone> for ContainerStd<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCone) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCone,
+    ) -> Result<(), VoroError> {
         let w0 = wall_cone_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallEllipsoid> for ContainerStd<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallEllipsoid,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_ellipsoid_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallTorus> for ContainerStd<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallTorus,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_torus_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallBox> for ContainerStd<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallBox,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_box_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallPolyhedron> for ContainerStd<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPolyhedron,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_polyhedron_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallCustom> for ContainerStd<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCustom,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls = true;
+        let w0 = wall_custom_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallSphere> for ContainerRad<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallSphere) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallSphere,
+    ) -> Result<(), VoroError> {
         let w0 = wall_sphere_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallPlane> for ContainerRad<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallPlane) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPlane,
+    ) -> Result<(), VoroError> {
         let w0 = wall_plane_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCylinder> for ContainerRad<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCylinder) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCylinder,
+    ) -> Result<(), VoroError> {
         let w0 =
             wall_cylinder_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCone> for ContainerRad<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCone) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCone,
+    ) -> Result<(), VoroError> {
         let w0 = wall_cone_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallEllipsoid> for ContainerRad<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallEllipsoid,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_ellipsoid_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallTorus> for ContainerRad<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallTorus,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_torus_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallBox> for ContainerRad<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallBox,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_box_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallPolyhedron> for ContainerRad<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPolyhedron,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_polyhedron_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallCustom> for ContainerRad<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCustom,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls = true;
+        let w0 = wall_custom_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, WallList<'a>> for ContainerStd<'a> {
-    fn add_walls(&mut self, walls: &mut WallList<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut WallList<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
             self.inner
                 .pin_mut()
-                .add_walls(walls.inner.pin_mut())
+                .add_walls(walls.inner.pin_mut())?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerStd<'a>> for ContainerStd<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerStd<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerStd<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -724,13 +1618,18 @@ impl<'a> Walls3<'a, ContainerStd<'a>> for ContainerStd<'a> {
                 container_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerRad<'a>> for ContainerStd<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerRad<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerRad<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -738,25 +1637,35 @@ impl<'a> Walls3<'a, ContainerRad<'a>> for ContainerStd<'a> {
                 container_poly_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, WallList<'a>> for ContainerRad<'a> {
-    fn add_walls(&mut self, walls: &mut WallList<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut WallList<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
             self.inner
                 .pin_mut()
-                .add_walls(walls.inner.pin_mut())
+                .add_walls(walls.inner.pin_mut())?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerStd<'a>> for ContainerRad<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerStd<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerStd<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -764,13 +1673,18 @@ impl<'a> Walls3<'a, ContainerStd<'a>> for ContainerRad<'a> {
                 container_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerRad<'a>> for ContainerRad<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerRad<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerRad<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -778,39 +1692,886 @@ impl<'a> Walls3<'a, ContainerRad<'a>> for ContainerRad<'a> {
                 container_poly_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls<'a> for ContainerStd<'a> {}
 impl<'a> Walls<'a> for ContainerRad<'a> {}
 
-/// A part of trait `Container` whose parameter does not depends any type.
-pub trait Container0 {
-    /// This function tests to see if a given vector lies within the container
-    /// bounds and any walls.
-    ///
-    /// *  `xyz`: the position vector to be tested.
-    ///
-    /// Return true if the point is inside the container, false if the point is
-    /// outside.
-    fn point_inside(&mut self, xyz: DVec3) -> bool;
+/// Pins the C++ object behind a raw pointer.
+///
+/// voro++ needs a mutable reference to a container to create a loop, to
+/// compute a cell or to draw, although none of these moves the particles
+/// stored in it; computing a cell only updates the scratch space of the
+/// container. This lets those routines take a shared reference on the Rust
+/// side, so that a loop can borrow its container while cells are computed.
+///
+/// The containers and `ParticleMarker` call this only in their
+/// `inner_pin` methods.
+///
+/// # Safety
+///
+/// `ptr` must point to a live object for `'p`, and the returned reference
+/// must not be used at the same time as any other mutable reference to the
+/// object. This holds when `ptr` comes from a shared reference to a type
+/// that is not `Sync`, and the returned reference does not outlive the
+/// shared one: mutation needs `&mut self`, which cannot coexist with the
+/// shared reference, and no other thread can hold a shared reference, so
+/// the temporary references made on this thread are used one at a time.
+pub(crate) unsafe fn pin_shared<'p, T>(
+    ptr: *mut T,
+) -> Pin<&'p mut T> {
+    Pin::new_unchecked(&mut *ptr)
+}
 
-    /// Sums up the total number of stored particles.
-    ///
-    /// Return the number of particles.
-    fn total_particles(&mut self) -> i32;
+/// Checks that a loop was created from the container owning `inner`.
+fn check_owner<T: UniquePtrTarget>(
+    inner: &UniquePtr<T>,
+    owner: usize,
+) -> Result<(), VoroError> {
+    if inner.as_ptr() as usize != owner {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            "the loop was created from another container",
+        ));
+    }
+    Ok(())
+}
 
-    /// Clears a container of particles.
-    fn clear(&mut self);
+/// A Voronoi cell computed by a container, together with the particle
+/// that it belongs to.
+pub struct CellRecord<T> {
+    /// The ID of the particle.
+    pub id: i32,
+    /// The position of the particle.
+    pub position: DVec3,
+    /// The radius of the particle. For a container without radius
+    /// information, this is the default radius 0.5 used by voro++.
+    pub radius: f64,
+    /// The Voronoi cell, with vertices relative to `position`.
+    pub cell: T,
+}
 
-    /// Put a particle into the correct region of the container.
-    ///
+/// An iterator over the Voronoi cells computed by a container.
+///
+/// This struct is created by the `cells` and `cells_in` methods of the
+/// containers. It drives the `start`/`inc` protocol of the underlying
+/// loop, so that an empty container simply yields nothing. It panics if
+/// voro++ reports a fatal error, see `TryCells`.
+pub struct Cells<'c, C, T, L>(TryCells<'c, C, T, L>);
+
+impl<'c, C, T, L> Cells<'c, C, T, L> {
+    fn new(container: &'c C, r#loop: L) -> Self {
+        Self(TryCells::new(container, r#loop))
+    }
+}
+
+impl<'c, C, T, L> Iterator for Cells<'c, C, T, L>
+where
+    C: Container2<T, L>,
+    T: VoroCell,
+    L: ContainerLoop,
+{
+    type Item = CellRecord<T>;
+
+    fn next(&mut self) -> Option<CellRecord<T>> {
+        self.0
+            .next()
+            .map(|r| r.unwrap_or_else(|e| panic!("{e}")))
+    }
+}
+
+/// A fallible iterator over the Voronoi cells computed by a container.
+///
+/// This struct is created by the `try_cells` and `try_cells_in` methods
+/// of the containers. It yields the fatal errors reported by voro++
+/// instead of panicking, and stops after the first one.
+pub struct TryCells<'c, C, T, L> {
+    container: &'c C,
+    r#loop: L,
+    started: bool,
+    finished: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<'c, C, T, L> TryCells<'c, C, T, L> {
+    fn new(container: &'c C, r#loop: L) -> Self {
+        Self {
+            container,
+            r#loop,
+            started: false,
+            finished: false,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'c, C, T, L> Iterator for TryCells<'c, C, T, L>
+where
+    C: Container2<T, L>,
+    T: VoroCell,
+    L: ContainerLoop,
+{
+    type Item = Result<CellRecord<T>, VoroError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let found = if self.started {
+                self.r#loop.inc()
+            } else {
+                self.started = true;
+                self.r#loop.start()
+            };
+            if !found {
+                self.finished = true;
+                break;
+            }
+            match self
+                .container
+                .try_compute_cell(&mut self.r#loop)
+            {
+                Ok(Some(cell)) => {
+                    return Some(Ok(CellRecord {
+                        id: self.r#loop.particle_id(),
+                        position: self.r#loop.position(),
+                        radius: self.r#loop.radius(),
+                        cell,
+                    }));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a> ContainerStd<'a> {
+    /// Returns an iterator over the Voronoi cells of all particles in
+    /// the container.
+    ///
+    /// Particles whose cell is removed entirely by a wall are skipped.
+    pub fn cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> Cells<'c, Self, T, LoopAll<'c>>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        let r#loop = LoopAll::of_container_std(self);
+        Cells::new(self, r#loop)
+    }
+
+    /// Computes the Voronoi cells of all particles in the container
+    /// and collects them into a `Tessellation`, which can be kept after
+    /// the container is dropped.
+    pub fn tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Tessellation
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        Tessellation::from_cells(self.cells::<T>())
+    }
+
+    /// Computes the cross sections of the Voronoi cells of all particles
+    /// in the container by a plane.
+    ///
+    /// * `plane`: the plane in the global coordinate system.
+    pub fn slice<'c>(&'c self, plane: Plane) -> Slice
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>,
+    {
+        Slice::from_cells(
+            self.cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Computes the Voronoi cells of all particles in the container and
+    /// prints them with a voro++ custom output format, one line per
+    /// particle. Neighbor information is only tracked when the format
+    /// prints it.
+    ///
+    /// * `format`: the parsed format.
+    /// * `w`: the writer to output to.
+    pub fn print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>
+            + Container2<VoroCellNbr, LoopAll<'c>>,
+    {
+        if format.needs_neighbors() {
+            format
+                .write_cells(self.cells::<VoroCellNbr>(), w)
+        } else {
+            format
+                .write_cells(self.cells::<VoroCellSgl>(), w)
+        }
+    }
+
+    /// Returns an iterator over the Voronoi cells of the particles
+    /// visited by a loop.
+    ///
+    /// * `r#loop`: the loop class to use. It should be created from this
+    /// container.
+    ///
+    /// Particles whose cell is removed entirely by a wall are skipped.
+    pub fn cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> Cells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        Cells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells`, whose iterator yields the fatal
+    /// errors reported by voro++ instead of panicking.
+    pub fn try_cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> TryCells<'c, Self, T, LoopAll<'c>>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        let r#loop = LoopAll::of_container_std(self);
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells_in`, see `try_cells`.
+    pub fn try_cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> TryCells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `tessellate`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Result<Tessellation, VoroError>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        Tessellation::try_from_cells(self.try_cells::<T>())
+    }
+
+    /// Fallible version of `slice`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_slice<'c>(
+        &'c self,
+        plane: Plane,
+    ) -> Result<Slice, VoroError>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>,
+    {
+        Slice::try_from_cells(
+            self.try_cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Fallible version of `print_custom`.
+    ///
+    /// Return an error if writing fails, or an error wrapping a
+    /// `VoroError` if voro++ reports a fatal error. The lines of the cells
+    /// before the failing one are written.
+    pub fn try_print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>
+            + Container2<VoroCellNbr, LoopAll<'c>>,
+    {
+        if format.needs_neighbors() {
+            format.try_write_cells(
+                self.try_cells::<VoroCellNbr>(),
+                w,
+            )
+        } else {
+            format.try_write_cells(
+                self.try_cells::<VoroCellSgl>(),
+                w,
+            )
+        }
+    }
+}
+
+impl<'a> ContainerRad<'a> {
+    /// Returns an iterator over the Voronoi cells of all particles in
+    /// the container.
+    ///
+    /// Particles whose cell is removed entirely by a wall are skipped.
+    pub fn cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> Cells<'c, Self, T, LoopAll<'c>>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        let r#loop = LoopAll::of_container_rad(self);
+        Cells::new(self, r#loop)
+    }
+
+    /// Computes the Voronoi cells of all particles in the container
+    /// and collects them into a `Tessellation`, which can be kept after
+    /// the container is dropped.
+    pub fn tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Tessellation
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        Tessellation::from_cells(self.cells::<T>())
+    }
+
+    /// Computes the cross sections of the Voronoi cells of all particles
+    /// in the container by a plane.
+    ///
+    /// * `plane`: the plane in the global coordinate system.
+    pub fn slice<'c>(&'c self, plane: Plane) -> Slice
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>,
+    {
+        Slice::from_cells(
+            self.cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Computes the Voronoi cells of all particles in the container and
+    /// prints them with a voro++ custom output format, one line per
+    /// particle. Neighbor information is only tracked when the format
+    /// prints it.
+    ///
+    /// * `format`: the parsed format.
+    /// * `w`: the writer to output to.
+    pub fn print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>
+            + Container2<VoroCellNbr, LoopAll<'c>>,
+    {
+        if format.needs_neighbors() {
+            format
+                .write_cells(self.cells::<VoroCellNbr>(), w)
+        } else {
+            format
+                .write_cells(self.cells::<VoroCellSgl>(), w)
+        }
+    }
+
+    /// Returns an iterator over the Voronoi cells of the particles
+    /// visited by a loop.
+    ///
+    /// * `r#loop`: the loop class to use. It should be created from this
+    /// container.
+    ///
+    /// Particles whose cell is removed entirely by a wall are skipped.
+    pub fn cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> Cells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        Cells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells`, whose iterator yields the fatal
+    /// errors reported by voro++ instead of panicking.
+    pub fn try_cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> TryCells<'c, Self, T, LoopAll<'c>>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        let r#loop = LoopAll::of_container_rad(self);
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells_in`, see `try_cells`.
+    pub fn try_cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> TryCells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `tessellate`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Result<Tessellation, VoroError>
+    where
+        Self: Container2<T, LoopAll<'c>>,
+    {
+        Tessellation::try_from_cells(self.try_cells::<T>())
+    }
+
+    /// Fallible version of `slice`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_slice<'c>(
+        &'c self,
+        plane: Plane,
+    ) -> Result<Slice, VoroError>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>,
+    {
+        Slice::try_from_cells(
+            self.try_cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Fallible version of `print_custom`.
+    ///
+    /// Return an error if writing fails, or an error wrapping a
+    /// `VoroError` if voro++ reports a fatal error. The lines of the cells
+    /// before the failing one are written.
+    pub fn try_print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAll<'c>>
+            + Container2<VoroCellNbr, LoopAll<'c>>,
+    {
+        if format.needs_neighbors() {
+            format.try_write_cells(
+                self.try_cells::<VoroCellNbr>(),
+                w,
+            )
+        } else {
+            format.try_write_cells(
+                self.try_cells::<VoroCellSgl>(),
+                w,
+            )
+        }
+    }
+}
+
+impl ContainerPeriodicStd {
+    /// Returns an iterator over the Voronoi cells of all particles in
+    /// the container.
+    ///
+    /// A periodic container has no walls, so every particle yields a cell.
+    pub fn cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> Cells<'c, Self, T, LoopAllPeriodic<'c>>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        let r#loop =
+            LoopAllPeriodic::of_container_std(self);
+        Cells::new(self, r#loop)
+    }
+
+    /// Computes the Voronoi cells of all particles in the container
+    /// and collects them into a `Tessellation`, which can be kept after
+    /// the container is dropped.
+    pub fn tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Tessellation
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        Tessellation::from_cells(self.cells::<T>())
+    }
+
+    /// Computes the cross sections of the Voronoi cells of all particles
+    /// in the container by a plane.
+    ///
+    /// * `plane`: the plane in the global coordinate system.
+    pub fn slice<'c>(&'c self, plane: Plane) -> Slice
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>,
+    {
+        Slice::from_cells(
+            self.cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Computes the Voronoi cells of all particles in the container and
+    /// prints them with a voro++ custom output format, one line per
+    /// particle. Neighbor information is only tracked when the format
+    /// prints it.
+    ///
+    /// * `format`: the parsed format.
+    /// * `w`: the writer to output to.
+    pub fn print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+            + Container2<VoroCellNbr, LoopAllPeriodic<'c>>,
+    {
+        if format.needs_neighbors() {
+            format
+                .write_cells(self.cells::<VoroCellNbr>(), w)
+        } else {
+            format
+                .write_cells(self.cells::<VoroCellSgl>(), w)
+        }
+    }
+
+    /// Returns an iterator over the Voronoi cells of the particles
+    /// visited by a loop.
+    ///
+    /// * `r#loop`: the loop class to use. It should be created from this
+    /// container.
+    ///
+    /// A periodic container has no walls, so every particle visited by
+    /// the loop yields a cell.
+    pub fn cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> Cells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        Cells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells`, whose iterator yields the fatal
+    /// errors reported by voro++ instead of panicking.
+    pub fn try_cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> TryCells<'c, Self, T, LoopAllPeriodic<'c>>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        let r#loop =
+            LoopAllPeriodic::of_container_std(self);
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells_in`, see `try_cells`.
+    pub fn try_cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> TryCells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `tessellate`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Result<Tessellation, VoroError>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        Tessellation::try_from_cells(self.try_cells::<T>())
+    }
+
+    /// Fallible version of `slice`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_slice<'c>(
+        &'c self,
+        plane: Plane,
+    ) -> Result<Slice, VoroError>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>,
+    {
+        Slice::try_from_cells(
+            self.try_cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Fallible version of `print_custom`.
+    ///
+    /// Return an error if writing fails, or an error wrapping a
+    /// `VoroError` if voro++ reports a fatal error. The lines of the cells
+    /// before the failing one are written.
+    pub fn try_print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+            + Container2<VoroCellNbr, LoopAllPeriodic<'c>>,
+    {
+        if format.needs_neighbors() {
+            format.try_write_cells(
+                self.try_cells::<VoroCellNbr>(),
+                w,
+            )
+        } else {
+            format.try_write_cells(
+                self.try_cells::<VoroCellSgl>(),
+                w,
+            )
+        }
+    }
+}
+
+impl ContainerPeriodicRad {
+    /// Returns an iterator over the Voronoi cells of all particles in
+    /// the container.
+    ///
+    /// A periodic container has no walls, so every particle yields a cell.
+    pub fn cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> Cells<'c, Self, T, LoopAllPeriodic<'c>>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        let r#loop =
+            LoopAllPeriodic::of_container_rad(self);
+        Cells::new(self, r#loop)
+    }
+
+    /// Computes the Voronoi cells of all particles in the container
+    /// and collects them into a `Tessellation`, which can be kept after
+    /// the container is dropped.
+    pub fn tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Tessellation
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        Tessellation::from_cells(self.cells::<T>())
+    }
+
+    /// Computes the cross sections of the Voronoi cells of all particles
+    /// in the container by a plane.
+    ///
+    /// * `plane`: the plane in the global coordinate system.
+    pub fn slice<'c>(&'c self, plane: Plane) -> Slice
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>,
+    {
+        Slice::from_cells(
+            self.cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Computes the Voronoi cells of all particles in the container and
+    /// prints them with a voro++ custom output format, one line per
+    /// particle. Neighbor information is only tracked when the format
+    /// prints it.
+    ///
+    /// * `format`: the parsed format.
+    /// * `w`: the writer to output to.
+    pub fn print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+            + Container2<VoroCellNbr, LoopAllPeriodic<'c>>,
+    {
+        if format.needs_neighbors() {
+            format
+                .write_cells(self.cells::<VoroCellNbr>(), w)
+        } else {
+            format
+                .write_cells(self.cells::<VoroCellSgl>(), w)
+        }
+    }
+
+    /// Returns an iterator over the Voronoi cells of the particles
+    /// visited by a loop.
+    ///
+    /// * `r#loop`: the loop class to use. It should be created from this
+    /// container.
+    ///
+    /// A periodic container has no walls, so every particle visited by
+    /// the loop yields a cell.
+    pub fn cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> Cells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        Cells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells`, whose iterator yields the fatal
+    /// errors reported by voro++ instead of panicking.
+    pub fn try_cells<'c, T: VoroCell>(
+        &'c self,
+    ) -> TryCells<'c, Self, T, LoopAllPeriodic<'c>>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        let r#loop =
+            LoopAllPeriodic::of_container_rad(self);
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `cells_in`, see `try_cells`.
+    pub fn try_cells_in<'c, T: VoroCell, L: ContainerLoop>(
+        &'c self,
+        r#loop: L,
+    ) -> TryCells<'c, Self, T, L>
+    where
+        Self: Container2<T, L>,
+    {
+        TryCells::new(self, r#loop)
+    }
+
+    /// Fallible version of `tessellate`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_tessellate<'c, T: VoroCell>(
+        &'c self,
+    ) -> Result<Tessellation, VoroError>
+    where
+        Self: Container2<T, LoopAllPeriodic<'c>>,
+    {
+        Tessellation::try_from_cells(self.try_cells::<T>())
+    }
+
+    /// Fallible version of `slice`.
+    ///
+    /// Return the first fatal error reported by voro++.
+    pub fn try_slice<'c>(
+        &'c self,
+        plane: Plane,
+    ) -> Result<Slice, VoroError>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>,
+    {
+        Slice::try_from_cells(
+            self.try_cells::<VoroCellSgl>(),
+            plane,
+        )
+    }
+
+    /// Fallible version of `print_custom`.
+    ///
+    /// Return an error if writing fails, or an error wrapping a
+    /// `VoroError` if voro++ reports a fatal error. The lines of the cells
+    /// before the failing one are written.
+    pub fn try_print_custom<'c>(
+        &'c self,
+        format: &CustomFormat,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        Self: Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+            + Container2<VoroCellNbr, LoopAllPeriodic<'c>>,
+    {
+        if format.needs_neighbors() {
+            format.try_write_cells(
+                self.try_cells::<VoroCellNbr>(),
+                w,
+            )
+        } else {
+            format.try_write_cells(
+                self.try_cells::<VoroCellSgl>(),
+                w,
+            )
+        }
+    }
+}
+
+/// A part of trait `Container` whose parameter does not depends any type.
+pub trait Container0 {
+    /// This function tests to see if a given vector lies within the container
+    /// bounds and any walls.
+    ///
+    /// *  `xyz`: the position vector to be tested.
+    ///
+    /// Return true if the point is inside the container, false if the point is
+    /// outside.
+    fn point_inside(&mut self, xyz: DVec3) -> bool;
+
+    /// Sums up the total number of stored particles.
+    ///
+    /// Return the number of particles.
+    fn total_particles(&mut self) -> i32;
+
+    /// Clears a container of particles.
+    fn clear(&mut self);
+
+    /// Put a particle into the correct region of the container.
+    ///
     /// * `n`: the numerical ID of the inserted particle.
     /// * `xyz`: the position vector of the inserted particle.
     /// * `r`: the radius of the particle. This is ignored for `ContainerStd`.
-    fn put(&mut self, n: i32, xyz: DVec3, r: f64);
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_put`.
+    fn put(&mut self, n: i32, xyz: DVec3, r: f64) {
+        self.try_put(n, xyz, r)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `put`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// the memory limit of a grid block is exceeded.
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError>;
+
+    /// Puts many particles into the container.
+    ///
+    /// * `particles`: the ID, position and radius of each particle. The
+    ///   radius is ignored for `ContainerStd`.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_put_all`.
+    fn put_all(&mut self, particles: &[(i32, DVec3, f64)]) {
+        self.try_put_all(particles)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `put_all`.
+    ///
+    /// Return an error if voro++ reports a fatal error. The particles
+    /// before the failing one stay in the container.
+    fn try_put_all(
+        &mut self,
+        particles: &[(i32, DVec3, f64)],
+    ) -> Result<(), VoroError> {
+        for &(n, xyz, r) in particles {
+            self.try_put(n, xyz, r)?;
+        }
+        Ok(())
+    }
 
     /// Put a particle into the correct region of the container, also recording
     /// into which region it was stored.
@@ -819,19 +2580,46 @@ pub trait Container0 {
     /// * `n`: the numerical ID of the inserted particle.
     /// * `xyz`: the position vector of the inserted particle.
     /// * `r`: the radius of the particle. This is ignored for `ContainerStd`.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_put_with_marker`.
     fn put_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         n: i32,
         xyz: DVec3,
         r: f64,
-    );
+    ) {
+        self.try_put_with_marker(marker, n, xyz, r)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `put_with_marker`.
+    fn try_put_with_marker(
+        &mut self,
+        marker: &mut ParticleMarker,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError>;
 
     /// Calculates all of the Voronoi cells and sums their volumes. In most cases
     /// without walls, the sum of the Voronoi cell volumes should equal the volume
     /// of the container to numerical precision.
     /// Return the sum of all of the computed Voronoi volumes.
-    fn sum_cell_volumes(&mut self) -> f64;
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_sum_cell_volumes`.
+    fn sum_cell_volumes(&mut self) -> f64 {
+        self.try_sum_cell_volumes()
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `sum_cell_volumes`.
+    ///
+    /// Return an error if voro++ reports a fatal error while computing a
+    /// cell.
+    fn try_sum_cell_volumes(
+        &mut self,
+    ) -> Result<f64, VoroError>;
 
     /// Takes a vector and finds the particle whose Voronoi cell contains that
     /// vector. This is equivalent to finding the particle which is nearest to the
@@ -846,17 +2634,168 @@ pub trait Container0 {
     /// contains the vector. If the container is periodic,
     /// this may point to a particle in a periodic image of
     /// the primary domain.
+    ///
+    /// Panics if voro++ reports a fatal error, see
+    /// `try_find_voronoi_cell`.
     fn find_voronoi_cell(
         &mut self,
         xyz: DVec3,
-    ) -> Option<(i32, DVec3)>;
+    ) -> Option<(i32, DVec3)> {
+        self.try_find_voronoi_cell(xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `find_voronoi_cell`.
+    fn try_find_voronoi_cell(
+        &mut self,
+        xyz: DVec3,
+    ) -> Result<Option<(i32, DVec3)>, VoroError>;
+}
+
+impl<'a> Container0 for ContainerStd<'a> {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+
+    fn total_particles(&mut self) -> i32 {
+        self.inner.pin_mut().total_particles()
+    }
+
+    fn clear(&mut self) {
+        self.inner.pin_mut().clear()
+    }
+
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
+            .pin_mut()
+            .put(n, xyz[0], xyz[1], xyz[2])?)
+    }
+
+    fn try_put_with_marker(
+        &mut self,
+        marker: &mut ParticleMarker,
+        n: i32,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().put_with_particle_order(
+            marker.inner.pin_mut(),
+            n,
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+
+    fn try_sum_cell_volumes(
+        &mut self,
+    ) -> Result<f64, VoroError> {
+        Ok(self.inner.pin_mut().sum_cell_volumes()?)
+    }
+
+    fn try_find_voronoi_cell(
+        &mut self,
+        xyz: DVec3,
+    ) -> Result<Option<(i32, DVec3)>, VoroError> {
+        let mut pid = 0;
+        let mut rx = 0.0;
+        let mut ry = 0.0;
+        let mut rz = 0.0;
+        let b = self.inner.pin_mut().find_voronoi_cell(
+            xyz[0], xyz[1], xyz[2], &mut rx, &mut ry,
+            &mut rz, &mut pid,
+        )?;
+        if b {
+            Ok(Some((pid, [rx, ry, rz])))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a> Container0 for ContainerRad<'a> {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+
+    fn total_particles(&mut self) -> i32 {
+        self.inner.pin_mut().total_particles()
+    }
+
+    fn clear(&mut self) {
+        self.inner.pin_mut().clear()
+    }
+
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
+            .pin_mut()
+            .put(n, xyz[0], xyz[1], xyz[2], r)?)
+    }
+
+    fn try_put_with_marker(
+        &mut self,
+        marker: &mut ParticleMarker,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().put_with_particle_order(
+            marker.inner.pin_mut(),
+            n,
+            xyz[0],
+            xyz[1],
+            xyz[2],
+            r,
+        )?)
+    }
+
+    fn try_sum_cell_volumes(
+        &mut self,
+    ) -> Result<f64, VoroError> {
+        Ok(self.inner.pin_mut().sum_cell_volumes()?)
+    }
+
+    fn try_find_voronoi_cell(
+        &mut self,
+        xyz: DVec3,
+    ) -> Result<Option<(i32, DVec3)>, VoroError> {
+        let mut pid = 0;
+        let mut rx = 0.0;
+        let mut ry = 0.0;
+        let mut rz = 0.0;
+        let b = self.inner.pin_mut().find_voronoi_cell(
+            xyz[0], xyz[1], xyz[2], &mut rx, &mut ry,
+            &mut rz, &mut pid,
+        )?;
+        if b {
+            Ok(Some((pid, [rx, ry, rz])))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
-impl<'a> Container0 for ContainerStd<'a> {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
+impl Container0 for ContainerPeriodicStd {
+    /// Every point lies inside a fully periodic container, so this
+    /// always returns true.
+    fn point_inside(&mut self, _: DVec3) -> bool {
+        true
     }
 
     fn total_particles(&mut self) -> i32 {
@@ -867,34 +2806,44 @@ impl<'a> Container0 for ContainerStd<'a> {
         self.inner.pin_mut().clear()
     }
 
-    fn put(&mut self, n: i32, xyz: DVec3, _: f64) {
-        self.inner.pin_mut().put(n, xyz[0], xyz[1], xyz[2])
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
+            .pin_mut()
+            .put(n, xyz[0], xyz[1], xyz[2])?)
     }
 
-    fn put_with_marker(
+    fn try_put_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         n: i32,
         xyz: DVec3,
         _: f64,
-    ) {
-        self.inner.pin_mut().put_with_particle_order(
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().put_with_particle_order(
             marker.inner.pin_mut(),
             n,
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 
-    fn sum_cell_volumes(&mut self) -> f64 {
-        self.inner.pin_mut().sum_cell_volumes()
+    fn try_sum_cell_volumes(
+        &mut self,
+    ) -> Result<f64, VoroError> {
+        Ok(self.inner.pin_mut().sum_cell_volumes()?)
     }
 
-    fn find_voronoi_cell(
+    fn try_find_voronoi_cell(
         &mut self,
         xyz: DVec3,
-    ) -> Option<(i32, DVec3)> {
+    ) -> Result<Option<(i32, DVec3)>, VoroError> {
         let mut pid = 0;
         let mut rx = 0.0;
         let mut ry = 0.0;
@@ -902,20 +2851,20 @@ impl<'a> Container0 for ContainerStd<'a> {
         let b = self.inner.pin_mut().find_voronoi_cell(
             xyz[0], xyz[1], xyz[2], &mut rx, &mut ry,
             &mut rz, &mut pid,
-        );
+        )?;
         if b {
-            Some((pid, [rx, ry, rz]))
+            Ok(Some((pid, [rx, ry, rz])))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container0 for ContainerRad<'a> {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
+impl Container0 for ContainerPeriodicRad {
+    /// Every point lies inside a fully periodic container, so this
+    /// always returns true.
+    fn point_inside(&mut self, _: DVec3) -> bool {
+        true
     }
 
     fn total_particles(&mut self) -> i32 {
@@ -926,37 +2875,45 @@ impl<'a> Container0 for ContainerRad<'a> {
         self.inner.pin_mut().clear()
     }
 
-    fn put(&mut self, n: i32, xyz: DVec3, r: f64) {
-        self.inner
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .put(n, xyz[0], xyz[1], xyz[2], r)
+            .put(n, xyz[0], xyz[1], xyz[2], r)?)
     }
 
-    fn put_with_marker(
+    fn try_put_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         n: i32,
         xyz: DVec3,
         r: f64,
-    ) {
-        self.inner.pin_mut().put_with_particle_order(
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().put_with_particle_order(
             marker.inner.pin_mut(),
             n,
             xyz[0],
             xyz[1],
             xyz[2],
             r,
-        );
+        )?)
     }
 
-    fn sum_cell_volumes(&mut self) -> f64 {
-        self.inner.pin_mut().sum_cell_volumes()
+    fn try_sum_cell_volumes(
+        &mut self,
+    ) -> Result<f64, VoroError> {
+        Ok(self.inner.pin_mut().sum_cell_volumes()?)
     }
 
-    fn find_voronoi_cell(
+    fn try_find_voronoi_cell(
         &mut self,
         xyz: DVec3,
-    ) -> Option<(i32, DVec3)> {
+    ) -> Result<Option<(i32, DVec3)>, VoroError> {
         let mut pid = 0;
         let mut rx = 0.0;
         let mut ry = 0.0;
@@ -964,15 +2921,31 @@ impl<'a> Container0 for ContainerRad<'a> {
         let b = self.inner.pin_mut().find_voronoi_cell(
             xyz[0], xyz[1], xyz[2], &mut rx, &mut ry,
             &mut rz, &mut pid,
-        );
+        )?;
         if b {
-            Some((pid, [rx, ry, rz]))
+            Ok(Some((pid, [rx, ry, rz])))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
+impl<'a> ContainerImport for ContainerStd<'a> {
+    const HAS_RADIUS: bool = false;
+}
+
+impl<'a> ContainerImport for ContainerRad<'a> {
+    const HAS_RADIUS: bool = true;
+}
+
+impl ContainerImport for ContainerPeriodicStd {
+    const HAS_RADIUS: bool = false;
+}
+
+impl ContainerImport for ContainerPeriodicRad {
+    const HAS_RADIUS: bool = true;
+}
+
 /// A part of trait `Container` whose parameter depends on Voronoi cell type.
 pub trait Container1<T: VoroCell> {
     /// Computes the Voronoi cell for a ghost particle at a given location.
@@ -985,350 +2958,665 @@ pub trait Container1<T: VoroCell> {
     /// computed, if it is removed entirely by a wall or boundary
     /// condition, then the routine returns `None`. If a value is
     /// returned, it is the computed Voronoi cell.
+    ///
+    /// Panics if voro++ reports a fatal error, see
+    /// `try_compute_ghost_cell`.
     fn compute_ghost_cell(
         &mut self,
         xyz: DVec3,
         r: f64,
-    ) -> Option<T>;
+    ) -> Option<T> {
+        self.try_compute_ghost_cell(xyz, r)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `compute_ghost_cell`.
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<Option<T>, VoroError>;
 }
 
 impl<'a> Container1<VoroCellSgl> for ContainerStd<'a> {
-    fn compute_ghost_cell(
+    fn try_compute_ghost_cell(
         &mut self,
         xyz: DVec3,
         _: f64,
-    ) -> Option<VoroCellSgl> {
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
         let mut cell = VoroCellSgl::new_empty();
         let b = self.inner.pin_mut().compute_ghost_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
 impl<'a> Container1<VoroCellNbr> for ContainerStd<'a> {
-    fn compute_ghost_cell(
+    fn try_compute_ghost_cell(
         &mut self,
         xyz: DVec3,
         _: f64,
-    ) -> Option<VoroCellNbr> {
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
         let mut cell = VoroCellNbr::new_empty();
         let b = self.inner.pin_mut().compute_ghost_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        );
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a> Container1<VoroCellSgl> for ContainerRad<'a> {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        let mut cell = VoroCellSgl::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+            r,
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a> Container1<VoroCellNbr> for ContainerRad<'a> {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        let mut cell = VoroCellNbr::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+            r,
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Container1<VoroCellSgl> for ContainerPeriodicStd {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        let mut cell = VoroCellSgl::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Container1<VoroCellNbr> for ContainerPeriodicStd {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        let mut cell = VoroCellNbr::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Container1<VoroCellSgl> for ContainerPeriodicRad {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        let mut cell = VoroCellSgl::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+            r,
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Container1<VoroCellNbr> for ContainerPeriodicRad {
+    fn try_compute_ghost_cell(
+        &mut self,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        let mut cell = VoroCellNbr::new_empty();
+        let b = self.inner.pin_mut().compute_ghost_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+            r,
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A part of trait `Container` whose parameter depends on Voronoi cell type and container loop type.
+pub trait Container2<T: VoroCell, S: ContainerLoop> {
+    /// Computes the Voronoi cell for a particle currently being
+    /// referenced by a loop class.
+    ///
+    /// * `r#loop`: the loop class to use.
+    ///
+    /// Return some values if the cell was computed. If the cell cannot be
+    /// computed, if it is removed entirely by a wall or boundary
+    /// condition, then the routine returns `None`. If a value is returned,
+    /// it is the Voronoi cell class in which to store the computed cell.
+    ///
+    /// Panics if voro++ reports a fatal error, or if the loop was created
+    /// from another container, see `try_compute_cell`.
+    fn compute_cell(&self, r#loop: &mut S) -> Option<T> {
+        self.try_compute_cell(r#loop)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `compute_cell`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// the cell becomes broken during a plane cut or exceeds the vertex
+    /// memory limit. Return an error of kind `InvalidArgument` if the loop
+    /// was created from another container.
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut S,
+    ) -> Result<Option<T>, VoroError>;
+}
+
+impl<'a, 'c> Container2<VoroCellSgl, LoopAll<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAll<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellSgl::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_0(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, 'c> Container2<VoroCellNbr, LoopAll<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAll<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellNbr::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_1(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, 'c> Container2<VoroCellSgl, LoopSubset<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopSubset<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellSgl::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_2(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, 'c> Container2<VoroCellNbr, LoopSubset<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopSubset<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellNbr::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_3(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, 'c> Container2<VoroCellSgl, LoopMarked<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarked<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellSgl::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_4(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
+        if b {
+            Ok(Some(cell))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, 'c> Container2<VoroCellNbr, LoopMarked<'c>>
+    for ContainerStd<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarked<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
+        let mut cell = VoroCellNbr::new_empty();
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_5(
+            cell.inner.pin_mut(),
+            r#loop.inner.pin_mut(),
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container1<VoroCellSgl> for ContainerRad<'a> {
-    fn compute_ghost_cell(
-        &mut self,
-        xyz: DVec3,
-        r: f64,
-    ) -> Option<VoroCellSgl> {
+impl<'a, 'c> Container2<VoroCellSgl, LoopAll<'c>>
+    for ContainerRad<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAll<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_ghost_0(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_0(
             cell.inner.pin_mut(),
-            xyz[0],
-            xyz[1],
-            xyz[2],
-            r,
-        );
+            r#loop.inner.pin_mut(),
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container1<VoroCellNbr> for ContainerRad<'a> {
-    fn compute_ghost_cell(
-        &mut self,
-        xyz: DVec3,
-        r: f64,
-    ) -> Option<VoroCellNbr> {
+impl<'a, 'c> Container2<VoroCellNbr, LoopAll<'c>>
+    for ContainerRad<'a>
+{
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAll<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_ghost_1(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_1(
             cell.inner.pin_mut(),
-            xyz[0],
-            xyz[1],
-            xyz[2],
-            r,
-        );
+            r#loop.inner.pin_mut(),
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-/// A part of trait `Container` whose parameter depends on Voronoi cell type and container loop type.
-pub trait Container2<T: VoroCell, S: ContainerLoop> {
-    /// Computes the Voronoi cell for a particle currently being
-    /// referenced by a loop class.
-    ///
-    /// * `r#loop`: the loop class to use.
-    ///
-    /// Return some values if the cell was computed. If the cell cannot be
-    /// computed, if it is removed entirely by a wall or boundary
-    /// condition, then the routine returns `None`. If a value is returned,
-    /// it is the Voronoi cell class in which to store the computed cell.
-    fn compute_cell(&mut self, r#loop: &mut S)
-        -> Option<T>;
-}
-
-impl<'a> Container2<VoroCellSgl, LoopAll>
-    for ContainerStd<'a>
+impl<'a, 'c> Container2<VoroCellSgl, LoopSubset<'c>>
+    for ContainerRad<'a>
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopAll,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopSubset<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_0(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_2(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopAll>
-    for ContainerStd<'a>
+impl<'a, 'c> Container2<VoroCellNbr, LoopSubset<'c>>
+    for ContainerRad<'a>
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopAll,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopSubset<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_1(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_3(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellSgl, LoopSubset>
-    for ContainerStd<'a>
+impl<'a, 'c> Container2<VoroCellSgl, LoopMarked<'c>>
+    for ContainerRad<'a>
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopSubset,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarked<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_2(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_4(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopSubset>
-    for ContainerStd<'a>
+impl<'a, 'c> Container2<VoroCellNbr, LoopMarked<'c>>
+    for ContainerRad<'a>
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopSubset,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarked<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_3(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_5(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellSgl, LoopMarked>
-    for ContainerStd<'a>
+impl<'c> Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+    for ContainerPeriodicStd
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopMarked,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAllPeriodic<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_4(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_0(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopMarked>
-    for ContainerStd<'a>
+impl<'c> Container2<VoroCellNbr, LoopAllPeriodic<'c>>
+    for ContainerPeriodicStd
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopMarked,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAllPeriodic<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_5(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_1(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellSgl, LoopAll>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellSgl, LoopMarkedPeriodic<'c>>
+    for ContainerPeriodicStd
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopAll,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarkedPeriodic<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_0(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_2(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopAll>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellNbr, LoopMarkedPeriodic<'c>>
+    for ContainerPeriodicStd
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopAll,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarkedPeriodic<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_1(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_3(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellSgl, LoopSubset>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+    for ContainerPeriodicRad
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopSubset,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAllPeriodic<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_2(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_0(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopSubset>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellNbr, LoopAllPeriodic<'c>>
+    for ContainerPeriodicRad
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopSubset,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopAllPeriodic<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_3(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_1(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellSgl, LoopMarked>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellSgl, LoopMarkedPeriodic<'c>>
+    for ContainerPeriodicRad
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopMarked,
-    ) -> Option<VoroCellSgl> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarkedPeriodic<'c>,
+    ) -> Result<Option<VoroCellSgl>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellSgl::new_empty();
-        let b = self.inner.pin_mut().compute_cell_4(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_2(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
 
-impl<'a> Container2<VoroCellNbr, LoopMarked>
-    for ContainerRad<'a>
+impl<'c> Container2<VoroCellNbr, LoopMarkedPeriodic<'c>>
+    for ContainerPeriodicRad
 {
-    fn compute_cell(
-        &mut self,
-        r#loop: &mut LoopMarked,
-    ) -> Option<VoroCellNbr> {
+    fn try_compute_cell(
+        &self,
+        r#loop: &mut LoopMarkedPeriodic<'c>,
+    ) -> Result<Option<VoroCellNbr>, VoroError> {
+        check_owner(&self.inner, r#loop.owner)?;
         let mut cell = VoroCellNbr::new_empty();
-        let b = self.inner.pin_mut().compute_cell_5(
+        let inner = self.inner_pin();
+        let b = inner.compute_cell_3(
             cell.inner.pin_mut(),
             r#loop.inner.pin_mut(),
-        );
+        )?;
         if b {
-            Some(cell)
+            Ok(Some(cell))
         } else {
-            None
+            Ok(None)
         }
     }
 }
@@ -1345,24 +3633,44 @@ pub trait Container<'a>:
     Container0
     + Container1<VoroCellSgl>
     + Container1<VoroCellNbr>
-    + Container2<VoroCellSgl, LoopAll>
-    + Container2<VoroCellNbr, LoopAll>
-    + Container2<VoroCellSgl, LoopMarked>
-    + Container2<VoroCellNbr, LoopMarked>
-    + Container2<VoroCellSgl, LoopSubset>
-    + Container2<VoroCellNbr, LoopSubset>
+    + for<'c> Container2<VoroCellSgl, LoopAll<'c>>
+    + for<'c> Container2<VoroCellNbr, LoopAll<'c>>
+    + for<'c> Container2<VoroCellSgl, LoopMarked<'c>>
+    + for<'c> Container2<VoroCellNbr, LoopMarked<'c>>
+    + for<'c> Container2<VoroCellSgl, LoopSubset<'c>>
+    + for<'c> Container2<VoroCellNbr, LoopSubset<'c>>
     + Walls<'a>
 {
 }
 impl<'a> Container<'a> for ContainerStd<'a> {}
 impl<'a> Container<'a> for ContainerRad<'a> {}
 
+/// Trait for representing a particle system in a three-dimensional
+/// triclinic periodic domain.
+///
+/// This trait represents a system of particles in a periodic unit cell
+/// spanned by (bx, 0, 0), (bxy, by, 0) and (bxz, byz, bz). Unlike
+/// `Container`, it does not derive the `Walls` trait, since walls are not
+/// supported in periodic containers.
+pub trait ContainerPeriodic: Container0
+    + Container1<VoroCellSgl>
+    + Container1<VoroCellNbr>
+    + for<'c> Container2<VoroCellSgl, LoopAllPeriodic<'c>>
+    + for<'c> Container2<VoroCellNbr, LoopAllPeriodic<'c>>
+    + for<'c> Container2<VoroCellSgl, LoopMarkedPeriodic<'c>>
+    + for<'c> Container2<VoroCellNbr, LoopMarkedPeriodic<'c>>
+{
+}
+impl ContainerPeriodic for ContainerPeriodicStd {}
+impl ContainerPeriodic for ContainerPeriodicRad {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         cell::{VoroCell, VoroCellNbr},
-        wall::WallSphere,
+        error::VoroErrorKind,
+        wall::{WallPlane, WallSphere},
     };
 
     #[test]
@@ -1405,7 +3713,9 @@ mod tests {
         assert_eq!(c1.volume(), 2.0);
 
         wl2.add_walls(&mut wl1);
-        wl2.apply_walls(&mut c2, [0.0, 0.0, 0.0]);
+        assert!(wl2
+            .try_apply_walls(&mut c2, [0.0, 0.0, 0.0])
+            .unwrap());
         assert_eq!(c2.volume(), 2.0);
     }
 
@@ -1473,6 +3783,331 @@ mod tests {
         assert!(c.is_none());
     }
 
+    #[test]
+    fn container_periodic_test() {
+        let mut con = ContainerPeriodicStd::new(
+            [10.0, 10.0, 10.0],
+            [2.0, 3.0, 1.0],
+            [5, 5, 5],
+        );
+        con.put(0, [0.0, 0.0, 0.0], 0.0);
+        con.put(1, [1.0, 0.0, 0.0], 0.0);
+        con.put(2, [2.0, 3.0, 0.0], 0.0);
+        con.put(3, [3.0, 0.0, 7.0], 0.0);
+        con.put(4, [14.0, -2.0, 5.0], 0.0);
+        assert_eq!(con.total_particles(), 5);
+        assert!(
+            (con.sum_cell_volumes() - 1000.0).abs() < 1e-8
+        );
+
+        let c = con.find_voronoi_cell([2.0, 3.0, 0.0]);
+        assert!(c.is_some());
+        assert_eq!(c.unwrap().0, 2);
+
+        let c: Option<VoroCellNbr> =
+            con.compute_ghost_cell([5.0, 5.0, 5.0], 0.0);
+        assert!(c.is_some());
+
+        con.clear();
+        assert_eq!(con.total_particles(), 0);
+    }
+
+    #[test]
+    fn loop_periodic_test() {
+        let mut con = ContainerPeriodicStd::new(
+            [10.0, 10.0, 10.0],
+            [2.0, 3.0, 1.0],
+            [5, 5, 5],
+        );
+        let mut marker = ParticleMarker::new();
+        con.put(0, [0.0, 0.0, 0.0], 0.0);
+        con.put(1, [1.0, 0.0, 0.0], 0.0);
+        con.put_with_marker(
+            &mut marker,
+            2,
+            [2.0, 3.0, 0.0],
+            0.0,
+        );
+        con.put(3, [3.0, 0.0, 7.0], 0.0);
+        con.put_with_marker(
+            &mut marker,
+            4,
+            [14.0, -2.0, 5.0],
+            0.0,
+        );
+
+        let mut volume = 0.0;
+        let mut cl =
+            LoopAllPeriodic::of_container_std(&con);
+        assert!(cl.start());
+        loop {
+            let cell: Option<VoroCellNbr> =
+                con.compute_cell(&mut cl);
+            volume += cell.unwrap().volume();
+            if !cl.inc() {
+                break;
+            }
+        }
+        assert!((volume - 1000.0).abs() < 1e-8);
+
+        let mut ids = Vec::new();
+        let mut cl = LoopMarkedPeriodic::with_container_std(
+            &con, &marker,
+        );
+        assert!(cl.start());
+        loop {
+            let cell: Option<VoroCellSgl> =
+                con.compute_cell(&mut cl);
+            assert!(cell.is_some());
+            ids.push(cl.particle_id());
+            if !cl.inc() {
+                break;
+            }
+        }
+        assert_eq!(ids, vec![2, 4]);
+    }
+
+    #[test]
+    fn container_periodic_rad_test() {
+        let mut con = ContainerPeriodicRad::new(
+            [10.0, 10.0, 10.0],
+            [0.0, 0.0, 0.0],
+            [5, 5, 5],
+        );
+        con.put(0, [0.0, 0.0, 0.0], 0.1);
+        con.put(1, [1.0, 0.0, 0.0], 0.2);
+        con.put(2, [2.0, 3.0, 0.0], 0.3);
+        con.put(3, [3.0, 0.0, 7.0], 0.4);
+        assert_eq!(con.total_particles(), 4);
+        assert!(
+            (con.sum_cell_volumes() - 1000.0).abs() < 1e-8
+        );
+    }
+
+    #[test]
+    fn try_test() {
+        let mut con = ContainerStd::try_new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        )
+        .unwrap();
+        con.try_put(0, [0.0, 0.0, 0.0], 0.0).unwrap();
+        con.try_put(1, [1.0, 0.0, 0.0], 0.0).unwrap();
+        assert_eq!(con.total_particles(), 2);
+
+        let mut cl = LoopAll::of_container_std(&con);
+        assert!(cl.start());
+        let cell: Option<VoroCellSgl> =
+            con.try_compute_cell(&mut cl).unwrap();
+        assert!(cell.is_some());
+
+        let mut cell = cell.unwrap();
+        let v0 = cell.volume();
+        assert!(cell
+            .try_nplane([-1.0, 0.0, 0.0], 5)
+            .unwrap());
+        assert!(cell.volume() < v0);
+    }
+
+    #[test]
+    fn fatal_error_test() {
+        // voro++ stores at most 2048 walls, and reports a fatal memory
+        // error beyond that.
+        let mut walls: Vec<_> = (0..2049)
+            .map(|_| WallPlane::new([0.0, 0.0, 1.0], 20.0))
+            .collect();
+        let mut con = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        let results: Vec<_> = walls
+            .iter_mut()
+            .map(|w| con.try_add_wall(w))
+            .collect();
+        assert!(results[..2048].iter().all(|r| r.is_ok()));
+        let e = results[2048].clone().unwrap_err();
+        assert_eq!(e.kind(), VoroErrorKind::Memory);
+        assert_eq!(
+            e.message(),
+            "Wall memory allocation exceeded absolute maximum"
+        );
+
+        // the container is still usable after the error.
+        con.put(0, [0.0, 0.0, 0.0], 0.0);
+        assert!(
+            (con.sum_cell_volumes() - 8000.0).abs() < 1e-8
+        );
+    }
+
+    #[test]
+    fn cells_test() {
+        let mut wall =
+            WallSphere::new([0.0, 0.0, 0.0], 5.0);
+        let mut con = ContainerRad::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [4, 4, 4],
+            [false, false, false],
+        );
+        assert_eq!(con.cells::<VoroCellSgl>().count(), 0);
+
+        con.add_wall(&mut wall);
+        con.put(0, [0.0, 0.0, 0.0], 0.5);
+        con.put(1, [1.0, 0.0, 0.0], 0.7);
+        con.put(2, [2.0, 2.0, 0.0], 0.5);
+        // outside of the wall, so it is skipped.
+        con.put(3, [8.0, 8.0, 8.0], 0.5);
+
+        let mut ids = Vec::new();
+        for mut record in con.cells::<VoroCellNbr>() {
+            assert!(record.cell.volume() > 0.0);
+            if record.id == 1 {
+                assert_eq!(
+                    record.position,
+                    [1.0, 0.0, 0.0]
+                );
+                assert_eq!(record.radius, 0.7);
+            }
+            ids.push(record.id);
+        }
+        ids.sort();
+        assert_eq!(ids, vec![0, 1, 2]);
+
+        let mut marker = ParticleMarker::new();
+        con.put_with_marker(
+            &mut marker,
+            4,
+            [0.0, 3.0, 0.0],
+            0.5,
+        );
+        let cl =
+            LoopMarked::with_container_rad(&con, &marker);
+        let ids: Vec<i32> = con
+            .cells_in::<VoroCellSgl, _>(cl)
+            .map(|record| record.id)
+            .collect();
+        assert_eq!(ids, vec![4]);
+    }
+
+    #[test]
+    fn loop_owner_test() {
+        let mut con0 = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        let mut con1 = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con0.put(0, [0.0, 0.0, 0.0], 0.0);
+        con1.put(0, [0.0, 0.0, 0.0], 0.0);
+
+        let mut cl = LoopAll::of_container_std(&con0);
+        assert!(cl.start());
+        let cell: Result<Option<VoroCellSgl>, _> =
+            con1.try_compute_cell(&mut cl);
+        assert_eq!(
+            cell.err().map(|e| e.kind()),
+            Some(VoroErrorKind::InvalidArgument)
+        );
+        let cell: Option<VoroCellSgl> =
+            con0.compute_cell(&mut cl);
+        assert!(cell.is_some());
+    }
+
+    #[test]
+    fn try_cells_test() {
+        let mut con0 = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        let mut con1 = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con0.put(0, [0.0, 0.0, 0.0], 0.0);
+        con0.put(1, [5.0, 0.0, 0.0], 0.0);
+        con1.put(0, [0.0, 0.0, 0.0], 0.0);
+
+        let tess =
+            con0.try_tessellate::<VoroCellNbr>().unwrap();
+        assert_eq!(tess.cells().len(), 2);
+        let ids: Vec<i32> = con0
+            .try_cells::<VoroCellSgl>()
+            .map(|record| record.unwrap().id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+
+        // a loop of another container is rejected, and the iterator stops
+        // after the error.
+        let cl = LoopAll::of_container_std(&con0);
+        let mut cells =
+            con1.try_cells_in::<VoroCellSgl, _>(cl);
+        assert_eq!(
+            cells.next().map(|r| r.err().map(|e| e.kind())),
+            Some(Some(VoroErrorKind::InvalidArgument))
+        );
+        assert!(cells.next().is_none());
+
+        let format = CustomFormat::new("%i %n");
+        let mut out = Vec::new();
+        con0.try_print_custom(&format, &mut out).unwrap();
+        assert_eq!(
+            out.iter().filter(|&&b| b == b'\n').count(),
+            2
+        );
+    }
+
+    #[test]
+    fn invalid_argument_test() {
+        let con = ContainerStd::try_new(
+            [10.0, -10.0, -10.0],
+            [-10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        assert_eq!(
+            con.err().map(|e| e.kind()),
+            Some(VoroErrorKind::InvalidArgument)
+        );
+        let con = ContainerRad::try_new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 0, 2],
+            [false, false, false],
+        );
+        assert!(con.is_err());
+        let con = ContainerStd::try_new_with_memory(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+            0,
+        );
+        assert!(con.is_err());
+        let con = ContainerPeriodicStd::try_new(
+            [10.0, -10.0, 10.0],
+            [0.0, 0.0, 0.0],
+            [2, 2, 2],
+        );
+        assert!(con.is_err());
+        assert!(
+            ParticleMarker::try_new_with_memory(0).is_err()
+        );
+    }
+
     #[test]
     fn loop_test() {
         let mut con = ContainerStd::new(
@@ -1493,7 +4128,7 @@ mod tests {
         con.put(8, [4.0, 4.0, 0.0], 0.0);
 
         let mut volume = 0.0;
-        let mut cl = LoopAll::of_container_std(&mut con);
+        let mut cl = LoopAll::of_container_std(&con);
         cl.start();
 
         loop {
@@ -1511,4 +4146,142 @@ mod tests {
 
         assert_eq!(volume, 8000.0);
     }
+
+    fn sphere_container() -> ContainerStd<'static> {
+        let mut con = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [4, 4, 4],
+            [false, false, false],
+        );
+        con.add_wall_owned(WallSphere::new(
+            [0.0, 0.0, 0.0],
+            5.0,
+        ));
+        con
+    }
+
+    #[test]
+    fn owned_wall_test() {
+        let mut con = sphere_container();
+        con.put(0, [1.0, 0.0, 0.0], 0.0);
+
+        let volume: f64 = con
+            .cells::<VoroCellSgl>()
+            .map(|mut record| record.cell.volume())
+            .sum();
+        // the sphere cuts the box at its tangent plane `x = 5`.
+        assert!((volume - 6000.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn try_owned_wall_test() {
+        let mut con = ContainerStd::new(
+            [-10.0, -10.0, -10.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        for _ in 0..2048 {
+            con.try_add_wall_owned(WallPlane::new(
+                [0.0, 0.0, 1.0],
+                20.0,
+            ))
+            .unwrap();
+        }
+        let e = con
+            .try_add_wall_owned(WallPlane::new(
+                [0.0, 0.0, 1.0],
+                20.0,
+            ))
+            .unwrap_err();
+        assert_eq!(e.kind(), VoroErrorKind::Memory);
+        // the rejected wall is not kept.
+        assert_eq!(con.owned_walls.len(), 2048);
+    }
+
+    #[test]
+    fn polyhedron_wall_test() {
+        // the octahedron |x| + |y| + |z| < 1, of volume 4 / 3.
+        let vertices = [
+            [1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0],
+        ];
+        let mut triangles = Vec::new();
+        for x in [0, 1] {
+            for y in [2, 3] {
+                for z in [4, 5] {
+                    triangles.push([x, y, z]);
+                }
+            }
+        }
+        let wall = WallPolyhedron::from_mesh(
+            &vertices, &triangles,
+        )
+        .unwrap();
+
+        let mut con = ContainerStd::new(
+            [-2.0, -2.0, -2.0],
+            [2.0, 2.0, 2.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.add_wall_owned(wall);
+        assert!(con.point_inside([0.2, 0.2, 0.2]));
+        assert!(!con.point_inside([0.5, 0.5, 0.5]));
+        // one particle in each octant, so that every face of the
+        // octahedron bounds a cell.
+        let mut n = 0;
+        for x in [-0.25, 0.25] {
+            for y in [-0.25, 0.25] {
+                for z in [-0.25, 0.25] {
+                    con.put(n, [x, y, z], 0.0);
+                    n += 1;
+                }
+            }
+        }
+
+        let tess = con.tessellate::<VoroCellNbr>();
+        assert_eq!(tess.len(), 8);
+        assert!((tess.volume() - 4.0 / 3.0).abs() < 1e-10);
+        let mut ids: Vec<i32> = tess
+            .iter()
+            .flat_map(|c| c.neighbors())
+            .filter(|&id| id < 0)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids, (-14..=-7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn send_test() {
+        let mut con = std::thread::spawn(|| {
+            let mut con = sphere_container();
+            con.put(0, [1.0, 0.0, 0.0], 0.0);
+            con
+        })
+        .join()
+        .unwrap();
+
+        let mut marker = ParticleMarker::new();
+        con.put_with_marker(
+            &mut marker,
+            1,
+            [-1.0, 0.0, 0.0],
+            0.0,
+        );
+        let volume = std::thread::spawn(move || {
+            con.cells::<VoroCellSgl>()
+                .map(|mut record| record.cell.volume())
+                .sum::<f64>()
+        })
+        .join()
+        .unwrap();
+        assert!((volume - 4000.0).abs() < 1e-8);
+    }
 }
@@ -8,6 +8,10 @@ pub mod ffi {
         type container = crate::container::ffi::container;
         type container_poly =
             crate::container::ffi::container_poly;
+        type container_periodic =
+            crate::container::ffi::container_periodic;
+        type container_periodic_poly =
+            crate::container::ffi::container_periodic_poly;
         type particle_order =
             crate::particle_marker::ffi::particle_order;
 
@@ -24,6 +28,14 @@ pub mod ffi {
         fn y(self: Pin<&mut c_loop_all>) -> f64;
         fn z(self: Pin<&mut c_loop_all>) -> f64;
         fn pid(self: Pin<&mut c_loop_all>) -> i32;
+        fn pos(
+            self: Pin<&mut c_loop_all>,
+            pid: &mut i32,
+            x: &mut f64,
+            y: &mut f64,
+            z: &mut f64,
+            r: &mut f64,
+        );
         fn start(self: Pin<&mut c_loop_all>) -> bool;
         fn inc(self: Pin<&mut c_loop_all>) -> bool;
 
@@ -40,6 +52,14 @@ pub mod ffi {
         fn y(self: Pin<&mut c_loop_subset>) -> f64;
         fn z(self: Pin<&mut c_loop_subset>) -> f64;
         fn pid(self: Pin<&mut c_loop_subset>) -> i32;
+        fn pos(
+            self: Pin<&mut c_loop_subset>,
+            pid: &mut i32,
+            x: &mut f64,
+            y: &mut f64,
+            z: &mut f64,
+            r: &mut f64,
+        );
         fn start(self: Pin<&mut c_loop_subset>) -> bool;
         fn inc(self: Pin<&mut c_loop_subset>) -> bool;
 
@@ -86,76 +106,159 @@ pub mod ffi {
         fn y(self: Pin<&mut c_loop_order>) -> f64;
         fn z(self: Pin<&mut c_loop_order>) -> f64;
         fn pid(self: Pin<&mut c_loop_order>) -> i32;
+        fn pos(
+            self: Pin<&mut c_loop_order>,
+            pid: &mut i32,
+            x: &mut f64,
+            y: &mut f64,
+            z: &mut f64,
+            r: &mut f64,
+        );
         fn start(self: Pin<&mut c_loop_order>) -> bool;
         fn inc(self: Pin<&mut c_loop_order>) -> bool;
+
+        type c_loop_all_periodic;
+        #[rust_name = "new_c_loop_all_periodic_0"]
+        fn construct(
+            con: Pin<&mut container_periodic>,
+        ) -> UniquePtr<c_loop_all_periodic>;
+        #[rust_name = "new_c_loop_all_periodic_1"]
+        fn construct(
+            con: Pin<&mut container_periodic_poly>,
+        ) -> UniquePtr<c_loop_all_periodic>;
+        fn x(self: Pin<&mut c_loop_all_periodic>) -> f64;
+        fn y(self: Pin<&mut c_loop_all_periodic>) -> f64;
+        fn z(self: Pin<&mut c_loop_all_periodic>) -> f64;
+        fn pid(self: Pin<&mut c_loop_all_periodic>) -> i32;
+        fn pos(
+            self: Pin<&mut c_loop_all_periodic>,
+            pid: &mut i32,
+            x: &mut f64,
+            y: &mut f64,
+            z: &mut f64,
+            r: &mut f64,
+        );
+        fn start(
+            self: Pin<&mut c_loop_all_periodic>,
+        ) -> bool;
+        fn inc(self: Pin<&mut c_loop_all_periodic>)
+            -> bool;
+
+        type c_loop_order_periodic;
+        #[rust_name = "new_c_loop_order_periodic_0"]
+        fn construct(
+            con: Pin<&mut container_periodic>,
+            vo: Pin<&mut particle_order>,
+        ) -> UniquePtr<c_loop_order_periodic>;
+        #[rust_name = "new_c_loop_order_periodic_1"]
+        fn construct(
+            con: Pin<&mut container_periodic_poly>,
+            vo: Pin<&mut particle_order>,
+        ) -> UniquePtr<c_loop_order_periodic>;
+        fn x(self: Pin<&mut c_loop_order_periodic>) -> f64;
+        fn y(self: Pin<&mut c_loop_order_periodic>) -> f64;
+        fn z(self: Pin<&mut c_loop_order_periodic>) -> f64;
+        fn pid(
+            self: Pin<&mut c_loop_order_periodic>,
+        ) -> i32;
+        fn pos(
+            self: Pin<&mut c_loop_order_periodic>,
+            pid: &mut i32,
+            x: &mut f64,
+            y: &mut f64,
+            z: &mut f64,
+            r: &mut f64,
+        );
+        fn start(
+            self: Pin<&mut c_loop_order_periodic>,
+        ) -> bool;
+        fn inc(
+            self: Pin<&mut c_loop_order_periodic>,
+        ) -> bool;
     }
 }
 
 use crate::{
-    container::{ContainerRad, ContainerStd},
+    container::{
+        ContainerPeriodicRad, ContainerPeriodicStd,
+        ContainerRad, ContainerStd,
+    },
     particle_marker::ParticleMarker,
 };
 use cxx::UniquePtr;
+use std::marker::PhantomData;
 
 type DVec3 = [f64; 3];
 type IVec3 = [i32; 3];
 
-pub struct LoopAll {
+pub struct LoopAll<'c> {
     pub(crate) inner: UniquePtr<ffi::c_loop_all>,
+    pub(crate) owner: usize,
+    phantom: PhantomData<&'c ()>,
 }
 
-impl LoopAll {
+impl<'c> LoopAll<'c> {
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn of_container_std(
-        container: &mut ContainerStd,
+        container: &'c ContainerStd,
     ) -> Self {
+        let inner =
+            ffi::new_c_loop_all_0(container.inner_pin());
         Self {
-            inner: ffi::new_c_loop_all_0(
-                container.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn of_container_rad(
-        container: &mut ContainerRad,
+        container: &'c ContainerRad,
     ) -> Self {
+        let inner =
+            ffi::new_c_loop_all_1(container.inner_pin());
         Self {
-            inner: ffi::new_c_loop_all_1(
-                container.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 }
 
-pub struct LoopSubset {
+pub struct LoopSubset<'c> {
     pub(crate) inner: UniquePtr<ffi::c_loop_subset>,
+    pub(crate) owner: usize,
+    phantom: PhantomData<&'c ()>,
 }
 
-impl LoopSubset {
+impl<'c> LoopSubset<'c> {
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn of_container_std(
-        container: &mut ContainerStd,
+        container: &'c ContainerStd,
     ) -> Self {
+        let inner =
+            ffi::new_c_loop_subset_0(container.inner_pin());
         Self {
-            inner: ffi::new_c_loop_subset_0(
-                container.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn of_container_rad(
-        container: &mut ContainerRad,
+        container: &'c ContainerRad,
     ) -> Self {
+        let inner =
+            ffi::new_c_loop_subset_1(container.inner_pin());
         Self {
-            inner: ffi::new_c_loop_subset_1(
-                container.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 
@@ -222,40 +325,134 @@ impl LoopSubset {
     }
 }
 
-pub struct LoopMarked {
+pub struct LoopMarked<'c> {
     pub(crate) inner: UniquePtr<ffi::c_loop_order>,
+    pub(crate) owner: usize,
+    phantom: PhantomData<&'c ()>,
 }
 
-impl LoopMarked {
+impl<'c> LoopMarked<'c> {
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn with_container_std(
-        container: &mut ContainerStd,
-        marker: &mut ParticleMarker,
+        container: &'c ContainerStd,
+        marker: &'c ParticleMarker,
     ) -> Self {
+        let inner = ffi::new_c_loop_order_0(
+            container.inner_pin(),
+            marker.inner_pin(),
+        );
         Self {
-            inner: ffi::new_c_loop_order_0(
-                container.inner.pin_mut(),
-                marker.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 
     /// The constructor copies several necessary constants from the
     /// base container class.
     pub fn with_container_rad(
-        container: &mut ContainerRad,
-        marker: &mut ParticleMarker,
+        container: &'c ContainerRad,
+        marker: &'c ParticleMarker,
     ) -> Self {
+        let inner = ffi::new_c_loop_order_1(
+            container.inner_pin(),
+            marker.inner_pin(),
+        );
         Self {
-            inner: ffi::new_c_loop_order_1(
-                container.inner.pin_mut(),
-                marker.inner.pin_mut(),
-            ),
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
         }
     }
 }
 
+pub struct LoopAllPeriodic<'c> {
+    pub(crate) inner: UniquePtr<ffi::c_loop_all_periodic>,
+    pub(crate) owner: usize,
+    phantom: PhantomData<&'c ()>,
+}
+
+impl<'c> LoopAllPeriodic<'c> {
+    /// The constructor copies several necessary constants from the
+    /// base periodic container class.
+    pub fn of_container_std(
+        container: &'c ContainerPeriodicStd,
+    ) -> Self {
+        let inner = ffi::new_c_loop_all_periodic_0(
+            container.inner_pin(),
+        );
+        Self {
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The constructor copies several necessary constants from the
+    /// base periodic container class.
+    pub fn of_container_rad(
+        container: &'c ContainerPeriodicRad,
+    ) -> Self {
+        let inner = ffi::new_c_loop_all_periodic_1(
+            container.inner_pin(),
+        );
+        Self {
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct LoopMarkedPeriodic<'c> {
+    pub(crate) inner: UniquePtr<ffi::c_loop_order_periodic>,
+    pub(crate) owner: usize,
+    phantom: PhantomData<&'c ()>,
+}
+
+impl<'c> LoopMarkedPeriodic<'c> {
+    /// The constructor copies several necessary constants from the
+    /// base periodic container class.
+    pub fn with_container_std(
+        container: &'c ContainerPeriodicStd,
+        marker: &'c ParticleMarker,
+    ) -> Self {
+        let inner = ffi::new_c_loop_order_periodic_0(
+            container.inner_pin(),
+            marker.inner_pin(),
+        );
+        Self {
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The constructor copies several necessary constants from the
+    /// base periodic container class.
+    pub fn with_container_rad(
+        container: &'c ContainerPeriodicRad,
+        marker: &'c ParticleMarker,
+    ) -> Self {
+        let inner = ffi::new_c_loop_order_periodic_1(
+            container.inner_pin(),
+            marker.inner_pin(),
+        );
+        Self {
+            inner,
+            owner: container.inner.as_ptr() as usize,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The interface of the loop classes in voro++.
+///
+/// A loop borrows the container that it was created from for its whole
+/// lifetime, so the container cannot be modified or dropped while the
+/// loop is alive. Passing a loop to `compute_cell` of another container is
+/// rejected at runtime.
 pub trait ContainerLoop {
     /// Returns the x position of the particle currently being
     /// considered by the loop.
@@ -279,6 +476,11 @@ pub trait ContainerLoop {
     /// by the loop.
     fn particle_id(&mut self) -> i32;
 
+    /// Returns the radius of the particle currently being considered
+    /// by the loop. For a container without radius information, this
+    /// is the default radius 0.5 used by voro++.
+    fn radius(&mut self) -> f64;
+
     /// Sets the class to consider the first particle.
     ///
     /// Return true if there is any particle to consider, false
@@ -292,7 +494,42 @@ pub trait ContainerLoop {
     fn inc(&mut self) -> bool;
 }
 
-impl ContainerLoop for LoopAll {
+impl<'c> ContainerLoop for LoopAll<'c> {
+    fn x(&mut self) -> f64 {
+        self.inner.pin_mut().x()
+    }
+
+    fn y(&mut self) -> f64 {
+        self.inner.pin_mut().y()
+    }
+
+    fn z(&mut self) -> f64 {
+        self.inner.pin_mut().z()
+    }
+
+    fn particle_id(&mut self) -> i32 {
+        self.inner.pin_mut().pid()
+    }
+
+    fn radius(&mut self) -> f64 {
+        let (mut pid, mut x, mut y, mut z, mut r) =
+            (0, 0.0, 0.0, 0.0, 0.0);
+        self.inner
+            .pin_mut()
+            .pos(&mut pid, &mut x, &mut y, &mut z, &mut r);
+        r
+    }
+
+    fn start(&mut self) -> bool {
+        self.inner.pin_mut().start()
+    }
+
+    fn inc(&mut self) -> bool {
+        self.inner.pin_mut().inc()
+    }
+}
+
+impl<'c> ContainerLoop for LoopSubset<'c> {
     fn x(&mut self) -> f64 {
         self.inner.pin_mut().x()
     }
@@ -309,6 +546,15 @@ impl ContainerLoop for LoopAll {
         self.inner.pin_mut().pid()
     }
 
+    fn radius(&mut self) -> f64 {
+        let (mut pid, mut x, mut y, mut z, mut r) =
+            (0, 0.0, 0.0, 0.0, 0.0);
+        self.inner
+            .pin_mut()
+            .pos(&mut pid, &mut x, &mut y, &mut z, &mut r);
+        r
+    }
+
     fn start(&mut self) -> bool {
         self.inner.pin_mut().start()
     }
@@ -318,7 +564,7 @@ impl ContainerLoop for LoopAll {
     }
 }
 
-impl ContainerLoop for LoopSubset {
+impl<'c> ContainerLoop for LoopMarked<'c> {
     fn x(&mut self) -> f64 {
         self.inner.pin_mut().x()
     }
@@ -335,6 +581,15 @@ impl ContainerLoop for LoopSubset {
         self.inner.pin_mut().pid()
     }
 
+    fn radius(&mut self) -> f64 {
+        let (mut pid, mut x, mut y, mut z, mut r) =
+            (0, 0.0, 0.0, 0.0, 0.0);
+        self.inner
+            .pin_mut()
+            .pos(&mut pid, &mut x, &mut y, &mut z, &mut r);
+        r
+    }
+
     fn start(&mut self) -> bool {
         self.inner.pin_mut().start()
     }
@@ -344,7 +599,7 @@ impl ContainerLoop for LoopSubset {
     }
 }
 
-impl ContainerLoop for LoopMarked {
+impl<'c> ContainerLoop for LoopAllPeriodic<'c> {
     fn x(&mut self) -> f64 {
         self.inner.pin_mut().x()
     }
@@ -361,6 +616,50 @@ impl ContainerLoop for LoopMarked {
         self.inner.pin_mut().pid()
     }
 
+    fn radius(&mut self) -> f64 {
+        let (mut pid, mut x, mut y, mut z, mut r) =
+            (0, 0.0, 0.0, 0.0, 0.0);
+        self.inner
+            .pin_mut()
+            .pos(&mut pid, &mut x, &mut y, &mut z, &mut r);
+        r
+    }
+
+    fn start(&mut self) -> bool {
+        self.inner.pin_mut().start()
+    }
+
+    fn inc(&mut self) -> bool {
+        self.inner.pin_mut().inc()
+    }
+}
+
+impl<'c> ContainerLoop for LoopMarkedPeriodic<'c> {
+    fn x(&mut self) -> f64 {
+        self.inner.pin_mut().x()
+    }
+
+    fn y(&mut self) -> f64 {
+        self.inner.pin_mut().y()
+    }
+
+    fn z(&mut self) -> f64 {
+        self.inner.pin_mut().z()
+    }
+
+    fn particle_id(&mut self) -> i32 {
+        self.inner.pin_mut().pid()
+    }
+
+    fn radius(&mut self) -> f64 {
+        let (mut pid, mut x, mut y, mut z, mut r) =
+            (0, 0.0, 0.0, 0.0, 0.0);
+        self.inner
+            .pin_mut()
+            .pos(&mut pid, &mut x, &mut y, &mut z, &mut r);
+        r
+    }
+
     fn start(&mut self) -> bool {
         self.inner.pin_mut().start()
     }
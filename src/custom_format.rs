@@ -0,0 +1,425 @@
+//! The custom output format of voro++.
+
+use crate::cell::VoroCell;
+use crate::container::CellRecord;
+use crate::error::VoroError;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+type DVec3 = [f64; 3];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Code(char),
+}
+
+/// A parsed voro++ custom output format, such as `"%i %q %v %n"`.
+///
+/// The format is parsed once and can then be applied to any number of
+/// cells. The output matches voro++'s `print_custom`, with floating point
+/// numbers printed like C's `%g`. The supported codes are:
+///
+/// * `%i`: the particle ID.
+/// * `%x`, `%y`, `%z`: the coordinates of the particle.
+/// * `%q`: the position of the particle, as `x y z`.
+/// * `%r`: the radius of the particle.
+/// * `%w`: the number of vertices.
+/// * `%p`: the vertices relative to the particle, as `(x,y,z)`.
+/// * `%P`: the vertices in the global coordinate system.
+/// * `%o`: the orders of the vertices.
+/// * `%m`: the maximum squared distance of a vertex from the particle.
+/// * `%g`: the number of edges.
+/// * `%E`: the total edge distance.
+/// * `%e`: the perimeters of the faces.
+/// * `%s`: the number of faces.
+/// * `%F`: the total surface area.
+/// * `%A`: the frequency table of the face orders.
+/// * `%a`: the orders of the faces.
+/// * `%f`: the areas of the faces.
+/// * `%t`: the vertex indices of the faces, as `(a,b,c)`.
+/// * `%l`: the normals of the faces, as `(x,y,z)`.
+/// * `%n`: the neighbors of the faces, empty without neighbor tracking.
+/// * `%v`: the volume.
+/// * `%c`: the centroid relative to the particle, as `x y z`.
+/// * `%C`: the centroid in the global coordinate system.
+///
+/// Like voro++, any other code is printed as it is, so `%%` is printed as
+/// `%%`, and a `%` at the end of the format is dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomFormat {
+    tokens: Vec<Token>,
+}
+
+impl CustomFormat {
+    /// Parses a format string.
+    ///
+    /// * `format`: the format string.
+    pub fn new(format: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut text = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some(code) if is_code(code) => {
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(
+                            std::mem::take(&mut text),
+                        ));
+                    }
+                    tokens.push(Token::Code(code));
+                }
+                Some(other) => {
+                    text.push('%');
+                    text.push(other);
+                }
+                None => {}
+            }
+        }
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        Self { tokens }
+    }
+
+    /// Returns true if the format prints neighbor information, so that the
+    /// cells must be computed with `VoroCellNbr`.
+    pub fn needs_neighbors(&self) -> bool {
+        self.tokens.contains(&Token::Code('n'))
+    }
+
+    /// Formats a cell, without a trailing newline.
+    ///
+    /// * `cell`: the cell, with vertices relative to the particle.
+    /// * `id`: the ID of the particle.
+    /// * `xyz`: the position of the particle.
+    /// * `r`: the radius of the particle.
+    pub fn format<T: VoroCell + ?Sized>(
+        &self,
+        cell: &mut T,
+        id: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> String {
+        let mut s = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Text(t) => s.push_str(t),
+                Token::Code(c) => format_code(
+                    &mut s, *c, cell, id, xyz, r,
+                ),
+            }
+        }
+        s
+    }
+
+    /// Formats computed cells, one line per cell, in the same way as
+    /// voro++'s `print_custom`.
+    ///
+    /// * `cells`: the cells, such as the ones returned by the `cells`
+    ///   method of the containers.
+    /// * `w`: the writer to output to.
+    pub fn write_cells<T, I>(
+        &self,
+        cells: I,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        T: VoroCell,
+        I: IntoIterator<Item = CellRecord<T>>,
+    {
+        self.try_write_cells(cells.into_iter().map(Ok), w)
+    }
+
+    /// Fallible version of `write_cells`, for the cells returned by the
+    /// `try_cells` method of the containers.
+    ///
+    /// Return an error wrapping the first `VoroError` among the cells,
+    /// after writing the lines of the cells before it.
+    pub fn try_write_cells<T, I>(
+        &self,
+        cells: I,
+        w: &mut dyn Write,
+    ) -> io::Result<()>
+    where
+        T: VoroCell,
+        I: IntoIterator<
+            Item = Result<CellRecord<T>, VoroError>,
+        >,
+    {
+        for record in cells {
+            let mut record =
+                record.map_err(io::Error::other)?;
+            let line = self.format(
+                &mut record.cell,
+                record.id,
+                record.position,
+                record.radius,
+            );
+            writeln!(w, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn is_code(c: char) -> bool {
+    "ixyzqrwpPomgEesFAaftlnvcC".contains(c)
+}
+
+fn format_code<T: VoroCell + ?Sized>(
+    s: &mut String,
+    code: char,
+    cell: &mut T,
+    id: i32,
+    xyz: DVec3,
+    r: f64,
+) {
+    match code {
+        'i' => {
+            let _ = write!(s, "{id}");
+        }
+        'x' => s.push_str(&fmt_g(xyz[0])),
+        'y' => s.push_str(&fmt_g(xyz[1])),
+        'z' => s.push_str(&fmt_g(xyz[2])),
+        'q' => s.push_str(&join(&xyz, " ", fmt_g)),
+        'r' => s.push_str(&fmt_g(r)),
+        'w' => {
+            let _ = write!(s, "{}", cell.vertices().len());
+        }
+        'p' => {
+            s.push_str(&positions(&cell.vertices_local()))
+        }
+        'P' => s.push_str(&positions(
+            &cell.vertices_global(xyz),
+        )),
+        'o' => s.push_str(&join(
+            &cell.vertex_orders(),
+            " ",
+            |i| i.to_string(),
+        )),
+        'm' => s.push_str(&fmt_g(
+            0.25 * cell.max_radius_squared(),
+        )),
+        'g' => {
+            let _ = write!(s, "{}", cell.number_of_edges());
+        }
+        'E' => {
+            s.push_str(&fmt_g(cell.total_edge_distance()))
+        }
+        'e' => s.push_str(&join(
+            &cell.face_perimeters(),
+            " ",
+            fmt_g,
+        )),
+        's' => {
+            let _ = write!(s, "{}", cell.number_of_faces());
+        }
+        'F' => s.push_str(&fmt_g(cell.surface_area())),
+        'A' => s.push_str(&join(
+            &cell.face_freq_table(),
+            " ",
+            |i| i.to_string(),
+        )),
+        'a' => s.push_str(&join(
+            &cell.face_orders(),
+            " ",
+            |i| i.to_string(),
+        )),
+        'f' => s.push_str(&join(
+            &cell.face_areas(),
+            " ",
+            fmt_g,
+        )),
+        't' => {
+            let faces: Vec<String> = cell
+                .faces()
+                .iter()
+                .map(|f| {
+                    format!(
+                        "({})",
+                        join(&f.vertices, ",", |i| i
+                            .to_string())
+                    )
+                })
+                .collect();
+            s.push_str(&faces.join(" "));
+        }
+        'l' => s.push_str(&positions(&cell.normals())),
+        'n' => {
+            if let Some(n) = cell.face_neighbors() {
+                s.push_str(&join(&n, " ", |i| {
+                    i.to_string()
+                }));
+            }
+        }
+        'v' => s.push_str(&fmt_g(cell.volume())),
+        'c' => {
+            s.push_str(&join(&cell.centroid(), " ", fmt_g))
+        }
+        'C' => {
+            let c = cell.centroid();
+            let c = [
+                xyz[0] + c[0],
+                xyz[1] + c[1],
+                xyz[2] + c[2],
+            ];
+            s.push_str(&join(&c, " ", fmt_g));
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn join<T: Copy>(
+    v: &[T],
+    sep: &str,
+    f: fn(T) -> String,
+) -> String {
+    v.iter().map(|&x| f(x)).collect::<Vec<_>>().join(sep)
+}
+
+/// Formats flat xyz triples as `(x,y,z)`, separated by spaces.
+fn positions(v: &[f64]) -> String {
+    v.chunks_exact(3)
+        .map(|p| format!("({})", join(p, ",", fmt_g)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats a number like C's `%g`, with six significant digits.
+pub(crate) fn fmt_g(x: f64) -> String {
+    const P: i32 = 6;
+    if x.is_nan() {
+        return "nan".to_string();
+    }
+    if x.is_infinite() {
+        return if x > 0.0 { "inf" } else { "-inf" }
+            .to_string();
+    }
+    if x == 0.0 {
+        return if x.is_sign_negative() {
+            "-0"
+        } else {
+            "0"
+        }
+        .to_string();
+    }
+    // The exponent after rounding to P significant digits.
+    let e = format!("{:.*e}", (P - 1) as usize, x);
+    let (mantissa, exp) = e.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+    if !(-4..P).contains(&exp) {
+        let mantissa = strip_zeros(mantissa);
+        let sign = if exp < 0 { '-' } else { '+' };
+        format!("{mantissa}e{sign}{:02}", exp.abs())
+    } else {
+        let s = format!("{:.*}", (P - 1 - exp) as usize, x);
+        strip_zeros(&s).to_string()
+    }
+}
+
+fn strip_zeros(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn fmt_g_test() {
+        assert_eq!(fmt_g(0.0), "0");
+        assert_eq!(fmt_g(1.0), "1");
+        assert_eq!(fmt_g(-0.5), "-0.5");
+        assert_eq!(fmt_g(1.0 / 3.0), "0.333333");
+        assert_eq!(fmt_g(123456.0), "123456");
+        assert_eq!(fmt_g(1234567.0), "1.23457e+06");
+        assert_eq!(fmt_g(999999.5), "1e+06");
+        assert_eq!(fmt_g(0.0001), "0.0001");
+        assert_eq!(fmt_g(0.00001234), "1.234e-05");
+        assert_eq!(fmt_g(2.5e-300), "2.5e-300");
+    }
+
+    #[test]
+    fn parse_test() {
+        let f = CustomFormat::new("%i: %v %% %k %");
+        assert!(!f.needs_neighbors());
+        assert!(
+            CustomFormat::new("%i %n").needs_neighbors()
+        );
+        let mut c0 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 2.0, 3.0],
+        );
+        assert_eq!(
+            c0.format_custom(&f, 7, [0.0; 3], 0.5),
+            "7: 6 %% %k "
+        );
+    }
+
+    #[test]
+    fn format_test() {
+        let mut c0 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let f = CustomFormat::new(
+            "%i %q %r %w %s %g %v %F %E %c %C",
+        );
+        assert_eq!(
+            c0.format_custom(&f, 3, [1.0, 2.0, 3.0], 0.5),
+            "3 1 2 3 0.5 8 6 12 8 24 24 0 0 0 1 2 3"
+        );
+        let f = CustomFormat::new("%a|%A|%f|%o|%m");
+        assert_eq!(
+            c0.format_custom(&f, 0, [0.0; 3], 0.5),
+            "4 4 4 4 4 4|0 0 0 0 6|4 4 4 4 4 4|3 3 3 3 3 3 3 3|3"
+        );
+        let f = CustomFormat::new("%p");
+        let s = c0.format_custom(&f, 0, [0.0; 3], 0.5);
+        assert!(s.starts_with("(-1,-1,-1) "));
+        assert_eq!(s.matches('(').count(), 8);
+        let f = CustomFormat::new("%t");
+        let s = c0.format_custom(&f, 0, [0.0; 3], 0.5);
+        assert_eq!(s.matches('(').count(), 6);
+        assert_eq!(s.matches(',').count(), 18);
+    }
+
+    #[test]
+    fn print_custom_test() {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, 0.0], 0.0);
+        con.put(1, [0.5, 0.0, 0.0], 0.0);
+
+        let mut out = Vec::new();
+        con.print_custom(
+            &CustomFormat::new("%i %v %n"),
+            &mut out,
+        )
+        .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let line = lines
+            .iter()
+            .find(|l| l.starts_with("0 "))
+            .unwrap();
+        let fields: Vec<&str> = line.split(' ').collect();
+        assert_eq!(fields[1], "4");
+        assert_eq!(fields.len(), 8);
+        assert!(fields[2..].contains(&"1"));
+    }
+}
@@ -0,0 +1,95 @@
+// Runs the drawing routines of voro++ on a temporary file, so that their
+// output can be handed to a Rust writer. See src/draw.rs.
+
+#include <cstdint>
+#include <cstdio>
+#include <memory>
+#include <stdexcept>
+#include "rust/cxx.h"
+
+namespace voro
+{
+    template <class F>
+    inline void capture_output(F draw, rust::Vec<std::uint8_t> &out)
+    {
+        // closed on every path, including when `draw` throws.
+        std::unique_ptr<FILE, int (*)(FILE *)> file(tmpfile(), fclose);
+        if (!file)
+            throw std::runtime_error("could not create a temporary file");
+        FILE *fp = file.get();
+        draw(fp);
+        rewind(fp);
+        char buf[4096];
+        size_t n;
+        while ((n = fread(buf, 1, sizeof(buf), fp)) > 0)
+            for (size_t i = 0; i < n; i++)
+                out.push_back(buf[i]);
+        if (ferror(fp) != 0)
+            throw std::runtime_error("could not read the temporary file");
+    }
+
+    template <class v_cell>
+    inline void cell_draw_gnuplot(v_cell &c, double x, double y, double z,
+                                  rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { c.draw_gnuplot(x, y, z, fp); }, out);
+    }
+    template <class v_cell>
+    inline void cell_draw_pov(v_cell &c, double x, double y, double z,
+                              rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { c.draw_pov(x, y, z, fp); }, out);
+    }
+    template <class v_cell>
+    inline void cell_draw_pov_mesh(v_cell &c, double x, double y,
+                                   double z, rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { c.draw_pov_mesh(x, y, z, fp); }, out);
+    }
+
+    template <class c_class>
+    inline void con_draw_particles(c_class &con,
+                                   rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_particles(fp); }, out);
+    }
+    template <class c_class>
+    inline void con_draw_particles_pov(c_class &con,
+                                       rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_particles_pov(fp); }, out);
+    }
+    template <class c_class>
+    inline void con_draw_cells_gnuplot(c_class &con,
+                                       rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_cells_gnuplot(fp); }, out);
+    }
+    template <class c_class>
+    inline void con_draw_cells_pov(c_class &con,
+                                   rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_cells_pov(fp); }, out);
+    }
+    template <class c_class>
+    inline void con_draw_domain_gnuplot(c_class &con,
+                                        rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_domain_gnuplot(fp); }, out);
+    }
+    template <class c_class>
+    inline void con_draw_domain_pov(c_class &con,
+                                    rust::Vec<std::uint8_t> &out)
+    {
+        capture_output([&](FILE *fp)
+                       { con.draw_domain_pov(fp); }, out);
+    }
+}
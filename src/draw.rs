@@ -0,0 +1,440 @@
+//! Drawing of cells and containers in gnuplot and POV-Ray formats.
+
+#[cxx::bridge(namespace = "voro")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("voro_rs/src/boilerplate.hh");
+
+        type voronoicell = crate::cell::ffi::voronoicell;
+        type voronoicell_neighbor =
+            crate::cell::ffi::voronoicell_neighbor;
+        type container = crate::container::ffi::container;
+        type container_poly =
+            crate::container::ffi::container_poly;
+        type container_periodic =
+            crate::container::ffi::container_periodic;
+        type container_periodic_poly =
+            crate::container::ffi::container_periodic_poly;
+
+        #[rust_name = "cell_draw_gnuplot_0"]
+        fn cell_draw_gnuplot(
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "cell_draw_gnuplot_1"]
+        fn cell_draw_gnuplot(
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "cell_draw_pov_0"]
+        fn cell_draw_pov(
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "cell_draw_pov_1"]
+        fn cell_draw_pov(
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "cell_draw_pov_mesh_0"]
+        fn cell_draw_pov_mesh(
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "cell_draw_pov_mesh_1"]
+        fn cell_draw_pov_mesh(
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_0"]
+        fn con_draw_particles(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_1"]
+        fn con_draw_particles(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_2"]
+        fn con_draw_particles(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_3"]
+        fn con_draw_particles(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_pov_0"]
+        fn con_draw_particles_pov(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_pov_1"]
+        fn con_draw_particles_pov(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_pov_2"]
+        fn con_draw_particles_pov(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_particles_pov_3"]
+        fn con_draw_particles_pov(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_gnuplot_0"]
+        fn con_draw_cells_gnuplot(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_gnuplot_1"]
+        fn con_draw_cells_gnuplot(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_gnuplot_2"]
+        fn con_draw_cells_gnuplot(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_gnuplot_3"]
+        fn con_draw_cells_gnuplot(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_pov_0"]
+        fn con_draw_cells_pov(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_pov_1"]
+        fn con_draw_cells_pov(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_pov_2"]
+        fn con_draw_cells_pov(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_cells_pov_3"]
+        fn con_draw_cells_pov(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_gnuplot_0"]
+        fn con_draw_domain_gnuplot(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_gnuplot_1"]
+        fn con_draw_domain_gnuplot(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_gnuplot_2"]
+        fn con_draw_domain_gnuplot(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_gnuplot_3"]
+        fn con_draw_domain_gnuplot(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_pov_0"]
+        fn con_draw_domain_pov(
+            con: Pin<&mut container>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_pov_1"]
+        fn con_draw_domain_pov(
+            con: Pin<&mut container_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_pov_2"]
+        fn con_draw_domain_pov(
+            con: Pin<&mut container_periodic>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+        #[rust_name = "con_draw_domain_pov_3"]
+        fn con_draw_domain_pov(
+            con: Pin<&mut container_periodic_poly>,
+            out: &mut Vec<u8>,
+        ) -> Result<()>;
+    }
+}
+
+use crate::container::{
+    ContainerPeriodicRad, ContainerPeriodicStd,
+    ContainerRad, ContainerStd,
+};
+use std::io::{self, Write};
+use std::pin::Pin;
+
+/// Runs a voro++ drawing routine, which writes into a buffer on the C++
+/// side, and passes its output on to a writer.
+pub(crate) fn write_output<F>(
+    draw: F,
+    w: &mut dyn Write,
+) -> io::Result<()>
+where
+    F: FnOnce(&mut Vec<u8>) -> Result<(), cxx::Exception>,
+{
+    let mut out = Vec::new();
+    draw(&mut out).map_err(io::Error::other)?;
+    w.write_all(&out)
+}
+
+/// Runs a voro++ drawing routine of a container, and passes its output on
+/// to a writer.
+fn draw_container<T>(
+    con: Pin<&mut T>,
+    draw: fn(
+        Pin<&mut T>,
+        &mut Vec<u8>,
+    ) -> Result<(), cxx::Exception>,
+    w: &mut dyn Write,
+) -> io::Result<()> {
+    write_output(|out| draw(con, out), w)
+}
+
+/// Implements the drawing methods of a container with the drawing
+/// routines of its FFI type.
+macro_rules! impl_draw {
+    (
+        $container:ty,
+        $particles:path,
+        $particles_pov:path,
+        $cells_gnuplot:path,
+        $cells_pov:path,
+        $domain_gnuplot:path,
+        $domain_pov:path $(,)?
+    ) => {
+        impl $container {
+            /// Outputs the positions of the particles in a format that
+            /// gnuplot can plot, as lines of the particle ID and its
+            /// coordinates.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_particles(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $particles,
+                    w,
+                )
+            }
+
+            /// Outputs the particles as POV-Ray spheres.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_particles_pov(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $particles_pov,
+                    w,
+                )
+            }
+
+            /// Computes the Voronoi cells of all particles and outputs
+            /// their edges in gnuplot format.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_cells_gnuplot(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $cells_gnuplot,
+                    w,
+                )
+            }
+
+            /// Computes the Voronoi cells of all particles and outputs
+            /// their edges in POV-Ray format, as a collection of spheres
+            /// and cylinders.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_cells_pov(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $cells_pov,
+                    w,
+                )
+            }
+
+            /// Outputs the boundary of the domain in gnuplot format.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_domain_gnuplot(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $domain_gnuplot,
+                    w,
+                )
+            }
+
+            /// Outputs the boundary of the domain in POV-Ray format.
+            ///
+            /// * `w`: the writer to output to.
+            pub fn draw_domain_pov(
+                &self,
+                w: &mut dyn Write,
+            ) -> io::Result<()> {
+                draw_container(
+                    self.inner_pin(),
+                    $domain_pov,
+                    w,
+                )
+            }
+        }
+    };
+}
+
+impl_draw!(
+    ContainerStd<'_>,
+    ffi::con_draw_particles_0,
+    ffi::con_draw_particles_pov_0,
+    ffi::con_draw_cells_gnuplot_0,
+    ffi::con_draw_cells_pov_0,
+    ffi::con_draw_domain_gnuplot_0,
+    ffi::con_draw_domain_pov_0,
+);
+
+impl_draw!(
+    ContainerRad<'_>,
+    ffi::con_draw_particles_1,
+    ffi::con_draw_particles_pov_1,
+    ffi::con_draw_cells_gnuplot_1,
+    ffi::con_draw_cells_pov_1,
+    ffi::con_draw_domain_gnuplot_1,
+    ffi::con_draw_domain_pov_1,
+);
+
+impl_draw!(
+    ContainerPeriodicStd,
+    ffi::con_draw_particles_2,
+    ffi::con_draw_particles_pov_2,
+    ffi::con_draw_cells_gnuplot_2,
+    ffi::con_draw_cells_pov_2,
+    ffi::con_draw_domain_gnuplot_2,
+    ffi::con_draw_domain_pov_2,
+);
+
+impl_draw!(
+    ContainerPeriodicRad,
+    ffi::con_draw_particles_3,
+    ffi::con_draw_particles_pov_3,
+    ffi::con_draw_cells_gnuplot_3,
+    ffi::con_draw_cells_pov_3,
+    ffi::con_draw_domain_gnuplot_3,
+    ffi::con_draw_domain_pov_3,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn cell_draw_test() {
+        let mut c0 = VoroCellSgl::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        let mut out = Vec::new();
+        c0.draw_gnuplot([1.0, 0.0, 0.0], &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text
+            .lines()
+            .any(|l| l.starts_with("2 1 1")));
+
+        let mut c1 = VoroCellNbr::new(
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+        );
+        let mut out = Vec::new();
+        c1.draw_pov([0.0, 0.0, 0.0], &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.matches("sphere{").count(), 8);
+        assert_eq!(text.matches("cylinder{").count(), 12);
+
+        let mut out = Vec::new();
+        c1.draw_pov_mesh([0.0, 0.0, 0.0], &mut out)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("mesh2 {"));
+    }
+
+    #[test]
+    fn container_draw_test() {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, 0.0], 0.0);
+        con.put(1, [0.5, 0.0, 0.0], 0.0);
+
+        let mut out = Vec::new();
+        con.draw_particles(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text
+            .lines()
+            .any(|l| l.starts_with("0 -0.5 0 0")));
+
+        let mut out = Vec::new();
+        con.draw_particles_pov(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.matches("sphere{").count(), 2);
+
+        let mut out = Vec::new();
+        con.draw_cells_gnuplot(&mut out).unwrap();
+        assert!(!out.is_empty());
+        let mut out = Vec::new();
+        con.draw_cells_pov(&mut out).unwrap();
+        assert!(!out.is_empty());
+        let mut out = Vec::new();
+        con.draw_domain_gnuplot(&mut out).unwrap();
+        assert!(!out.is_empty());
+        let mut out = Vec::new();
+        con.draw_domain_pov(&mut out).unwrap();
+        assert!(!out.is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+//! Error type for fatal errors reported by voro++.
+
+use std::fmt;
+
+type DVec3 = [f64; 3];
+type IVec3 = [i32; 3];
+
+/// The kind of a `VoroError`.
+///
+/// The first four kinds correspond to the exit status codes that voro++
+/// passes to `voro_fatal_error`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VoroErrorKind {
+    /// A file could not be opened or read (`VOROPP_FILE_ERROR`).
+    File,
+    /// A memory limit was exceeded (`VOROPP_MEMORY_ERROR`).
+    Memory,
+    /// An internal consistency check failed, typically because a
+    /// Voronoi cell became broken during a plane cut
+    /// (`VOROPP_INTERNAL_ERROR`).
+    Internal,
+    /// Invalid command-line arguments (`VOROPP_CMD_LINE_ERROR`).
+    CommandLine,
+    /// An argument was rejected before reaching voro++, such as an
+    /// inverted bounding box or a non-positive grid count.
+    InvalidArgument,
+    /// Any other C++ exception, such as `std::bad_alloc`.
+    Other,
+}
+
+impl VoroErrorKind {
+    fn from_status(status: i32) -> Option<Self> {
+        match status {
+            1 => Some(Self::File),
+            2 => Some(Self::Memory),
+            3 => Some(Self::Internal),
+            4 => Some(Self::CommandLine),
+            _ => None,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::File => "file error",
+            Self::Memory => "memory error",
+            Self::Internal => "internal error",
+            Self::CommandLine => "command line error",
+            Self::InvalidArgument => "invalid argument",
+            Self::Other => "unexpected exception",
+        }
+    }
+}
+
+/// An error raised by voro++.
+///
+/// voro++ calls `exit()` when it meets a fatal error. This crate compiles
+/// voro++ so that such calls throw a C++ exception instead, which is
+/// turned into a `VoroError` by the `try_*` methods. The message of the
+/// error is the diagnostic of voro++, which voro++ also prints to the
+/// standard error stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoroError {
+    kind: VoroErrorKind,
+    message: String,
+}
+
+impl VoroError {
+    pub(crate) fn new(
+        kind: VoroErrorKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Return the kind of this error.
+    pub fn kind(&self) -> VoroErrorKind {
+        self.kind
+    }
+
+    /// Return the message of this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // a fatal error from voro++ carries its exit status and its
+    // diagnostic as "status:message", see `src/voro.cc`.
+    fn from_what(what: &str) -> Self {
+        let fatal =
+            what.split_once(':').and_then(|(s, m)| {
+                let kind = s
+                    .parse()
+                    .ok()
+                    .and_then(VoroErrorKind::from_status)?;
+                Some((kind, m))
+            });
+        match fatal {
+            Some((kind, "")) => {
+                Self::new(kind, kind.description())
+            }
+            Some((kind, message)) => {
+                Self::new(kind, message)
+            }
+            None => Self::new(VoroErrorKind::Other, what),
+        }
+    }
+}
+
+impl fmt::Display for VoroError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "voro++: {}", self.message)
+    }
+}
+
+impl std::error::Error for VoroError {}
+
+impl From<cxx::Exception> for VoroError {
+    fn from(e: cxx::Exception) -> Self {
+        Self::from_what(e.what())
+    }
+}
+
+/// Checks that `xyz_min` and `xyz_max` span a non-empty box.
+pub(crate) fn check_box(
+    xyz_min: DVec3,
+    xyz_max: DVec3,
+) -> Result<(), VoroError> {
+    if xyz_min
+        .iter()
+        .chain(&xyz_max)
+        .any(|v| !v.is_finite())
+    {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "bounds must be finite, got {xyz_min:?} and {xyz_max:?}"
+            ),
+        ));
+    }
+    if (0..3).any(|i| xyz_min[i] >= xyz_max[i]) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`xyz_min` must be less than `xyz_max` in each \
+                 direction, got {xyz_min:?} and {xyz_max:?}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the unit cell of a periodic container.
+pub(crate) fn check_unit_cell(
+    box_size: DVec3,
+    box_shear: DVec3,
+) -> Result<(), VoroError> {
+    if box_size.iter().any(|v| !(v.is_finite() && *v > 0.0))
+    {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`box_size` must be finite and positive, got {box_size:?}"
+            ),
+        ));
+    }
+    if box_shear.iter().any(|v| !v.is_finite()) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`box_shear` must be finite, got {box_shear:?}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that there is at least one grid block in each direction.
+pub(crate) fn check_grids(
+    grids: IVec3,
+) -> Result<(), VoroError> {
+    if grids.iter().any(|&n| n <= 0) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`grids` must be positive, got {grids:?}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the values of an argument are finite and positive.
+pub(crate) fn check_positive(
+    name: &str,
+    values: &[f64],
+) -> Result<(), VoroError> {
+    if values.iter().any(|v| !(v.is_finite() && *v > 0.0)) {
+        let got = match values {
+            [v] => v.to_string(),
+            _ => format!("{values:?}"),
+        };
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`{name}` must be finite and positive, got {got}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a point or a vector is finite, and nonzero if `nonzero`
+/// is true.
+pub(crate) fn check_vector(
+    name: &str,
+    v: DVec3,
+    nonzero: bool,
+) -> Result<(), VoroError> {
+    if v.iter().any(|x| !x.is_finite()) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!("`{name}` must be finite, got {v:?}"),
+        ));
+    }
+    if nonzero && v.iter().all(|&x| x == 0.0) {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!("`{name}` must be nonzero"),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that an initial memory allocation is positive.
+pub(crate) fn check_memory(
+    initial_memory: i32,
+) -> Result<(), VoroError> {
+    if initial_memory <= 0 {
+        return Err(VoroError::new(
+            VoroErrorKind::InvalidArgument,
+            format!(
+                "`initial_memory` must be positive, got {initial_memory}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_test() {
+        assert_eq!(
+            VoroErrorKind::from_status(2),
+            Some(VoroErrorKind::Memory)
+        );
+        assert_eq!(VoroErrorKind::from_status(0), None);
+
+        let e = VoroError::from_what(
+            "3:Lost track of a vertex",
+        );
+        assert_eq!(e.kind(), VoroErrorKind::Internal);
+        assert_eq!(e.message(), "Lost track of a vertex");
+        assert_eq!(
+            e.to_string(),
+            "voro++: Lost track of a vertex"
+        );
+        let e = VoroError::from_what("2:");
+        assert_eq!(e.kind(), VoroErrorKind::Memory);
+        assert_eq!(e.message(), "memory error");
+        let e = VoroError::from_what("std::bad_alloc");
+        assert_eq!(e.kind(), VoroErrorKind::Other);
+        assert_eq!(e.message(), "std::bad_alloc");
+        let e = VoroError::from_what("9:unknown status");
+        assert_eq!(e.kind(), VoroErrorKind::Other);
+    }
+
+    #[test]
+    fn check_test() {
+        let ok =
+            check_box([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+        assert!(ok.is_ok());
+        let e = check_box([0.0, 2.0, 0.0], [1.0, 1.0, 1.0])
+            .unwrap_err();
+        assert_eq!(
+            e.kind(),
+            VoroErrorKind::InvalidArgument
+        );
+        assert!(check_box(
+            [0.0, f64::NAN, 0.0],
+            [1.0, 1.0, 1.0]
+        )
+        .is_err());
+        assert!(check_unit_cell(
+            [1.0, 0.0, 1.0],
+            [0.0, 0.0, 0.0]
+        )
+        .is_err());
+        assert!(check_grids([1, 0, 1]).is_err());
+        assert!(check_memory(-1).is_err());
+        assert!(check_memory(8).is_ok());
+        let e = check_positive("r", &[-1.0]).unwrap_err();
+        assert_eq!(
+            e.message(),
+            "`r` must be finite and positive, got -1"
+        );
+        assert!(check_positive("r", &[1.0, f64::INFINITY])
+            .is_err());
+        assert!(check_vector("a", [0.0; 3], true).is_err());
+        assert!(check_vector("c", [0.0; 3], false).is_ok());
+    }
+}
@@ -0,0 +1,315 @@
+//! Reading of particles from voro++ text files.
+
+use crate::container::Container0;
+use crate::error::{VoroError, VoroErrorKind};
+use crate::particle_marker::ParticleMarker;
+use crate::pre_container::PreContainer;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+type DVec3 = [f64; 3];
+
+/// The radius given to particles read from files without a radius
+/// column, which is the default radius of voro++.
+const DEFAULT_RADIUS: f64 = 0.5;
+
+/// Reads particles from a voro++ text file, with one particle per line
+/// given as `id x y z`, or as `id x y z r` if `radius` is true. Blank
+/// lines are skipped.
+///
+/// * `reader`: the source of the file.
+/// * `radius`: whether the lines have a radius column.
+///
+/// Return an error of kind `File` naming the line that could not be
+/// read or parsed.
+pub fn read_particles<R: BufRead>(
+    reader: R,
+    radius: bool,
+) -> Result<Vec<(i32, DVec3, f64)>, VoroError> {
+    let columns = if radius { 5 } else { 4 };
+    let mut particles = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let error = |message: String| {
+            VoroError::new(
+                VoroErrorKind::File,
+                format!("line {}: {message}", i + 1),
+            )
+        };
+        let line =
+            line.map_err(|e| error(e.to_string()))?;
+        let fields: Vec<&str> =
+            line.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+        if fields.len() != columns {
+            return Err(error(format!(
+                "expected {columns} columns, found {}",
+                fields.len()
+            )));
+        }
+        let id: i32 = fields[0].parse().map_err(|_| {
+            error(format!(
+                "invalid particle ID {:?}",
+                fields[0]
+            ))
+        })?;
+        let mut values = [DEFAULT_RADIUS; 4];
+        for (v, field) in
+            values.iter_mut().zip(&fields[1..])
+        {
+            *v = field
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| {
+                    error(format!(
+                        "invalid number {field:?}"
+                    ))
+                })?;
+        }
+        let [x, y, z, r] = values;
+        particles.push((id, [x, y, z], r));
+    }
+    Ok(particles)
+}
+
+/// Reads particles from a voro++ text file at a path, see
+/// `read_particles`.
+///
+/// * `path`: the path of the file.
+/// * `radius`: whether the lines have a radius column.
+pub fn read_particles_path<P: AsRef<Path>>(
+    path: P,
+    radius: bool,
+) -> Result<Vec<(i32, DVec3, f64)>, VoroError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| {
+        VoroError::new(
+            VoroErrorKind::File,
+            format!("{}: {e}", path.display()),
+        )
+    })?;
+    read_particles(BufReader::new(file), radius).map_err(
+        |e| {
+            VoroError::new(
+                e.kind(),
+                format!(
+                    "{}: {}",
+                    path.display(),
+                    e.message()
+                ),
+            )
+        },
+    )
+}
+
+/// Importing of particles from voro++ text files into a container.
+pub trait ContainerImport: Container0 {
+    /// True if the particles of the container have radii, in which case
+    /// imported files have a radius column.
+    const HAS_RADIUS: bool;
+
+    /// Imports particles from a voro++ text file, with one particle per
+    /// line given as `id x y z`, or as `id x y z r` if the container has
+    /// radii. The whole file is read before any particle is put, so that
+    /// a malformed file leaves the container unchanged.
+    ///
+    /// * `reader`: the source of the file.
+    ///
+    /// Return an error naming the line that could not be parsed, or if
+    /// voro++ reports a fatal error.
+    fn import_reader<R: BufRead>(
+        &mut self,
+        reader: R,
+    ) -> Result<(), VoroError> {
+        let particles =
+            read_particles(reader, Self::HAS_RADIUS)?;
+        self.try_put_all(&particles)
+    }
+
+    /// Imports particles from a voro++ text file at a path, see
+    /// `import_reader`.
+    ///
+    /// * `path`: the path of the file.
+    fn import_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), VoroError> {
+        let particles =
+            read_particles_path(path, Self::HAS_RADIUS)?;
+        self.try_put_all(&particles)
+    }
+
+    /// Imports particles from a voro++ text file, also recording into
+    /// which region each particle was stored, see `import_reader`.
+    ///
+    /// * `marker`: the marker class in which to record the regions.
+    /// * `reader`: the source of the file.
+    fn import_reader_with_marker<R: BufRead>(
+        &mut self,
+        marker: &mut ParticleMarker,
+        reader: R,
+    ) -> Result<(), VoroError> {
+        let particles =
+            read_particles(reader, Self::HAS_RADIUS)?;
+        for (n, xyz, r) in particles {
+            self.try_put_with_marker(marker, n, xyz, r)?;
+        }
+        Ok(())
+    }
+}
+
+/// Importing of particles from voro++ text files into a pre-container.
+pub trait PreContainerImport<T>: PreContainer<T> {
+    /// True if the particles have radii, in which case imported files
+    /// have a radius column.
+    const HAS_RADIUS: bool;
+
+    /// Imports particles from a voro++ text file, with one particle per
+    /// line given as `id x y z`, or as `id x y z r` for
+    /// `PreContainerRad`. The whole file is read before any particle is
+    /// stored, so that a malformed file leaves the particles unchanged.
+    ///
+    /// * `reader`: the source of the file.
+    ///
+    /// Return an error naming the line that could not be parsed.
+    fn import_reader<R: BufRead>(
+        &mut self,
+        reader: R,
+    ) -> Result<(), VoroError> {
+        let particles =
+            read_particles(reader, Self::HAS_RADIUS)?;
+        self.put_all(&particles);
+        Ok(())
+    }
+
+    /// Imports particles from a voro++ text file at a path, see
+    /// `import_reader`.
+    ///
+    /// * `path`: the path of the file.
+    fn import_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), VoroError> {
+        let particles =
+            read_particles_path(path, Self::HAS_RADIUS)?;
+        self.put_all(&particles);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pre_container::{
+        PreContainer, PreContainerRad,
+    };
+    use crate::prelude::*;
+
+    #[test]
+    fn read_test() {
+        let text = "0 0.5 0.5 0.5\n\n 1\t1.5 0.5 0.5  \n";
+        let particles =
+            read_particles(text.as_bytes(), false).unwrap();
+        assert_eq!(
+            particles,
+            vec![
+                (0, [0.5, 0.5, 0.5], 0.5),
+                (1, [1.5, 0.5, 0.5], 0.5)
+            ]
+        );
+
+        let text = "0 0.5 0.5 0.5 0.25\n";
+        let particles =
+            read_particles(text.as_bytes(), true).unwrap();
+        assert_eq!(
+            particles,
+            vec![(0, [0.5, 0.5, 0.5], 0.25)]
+        );
+
+        let text = "0 0.5 0.5 0.5\n1 0.5 0.5\n";
+        let e = read_particles(text.as_bytes(), false)
+            .unwrap_err();
+        assert_eq!(e.kind(), VoroErrorKind::File);
+        assert_eq!(
+            e.message(),
+            "line 2: expected 4 columns, found 3"
+        );
+
+        let text = "0 0.5 0.5 0.5\n\nx 0.5 0.5 0.5\n";
+        let e = read_particles(text.as_bytes(), false)
+            .unwrap_err();
+        assert_eq!(
+            e.message(),
+            "line 3: invalid particle ID \"x\""
+        );
+
+        let text = "0 0.5 nan 0.5 1\n";
+        let e = read_particles(text.as_bytes(), true)
+            .unwrap_err();
+        assert_eq!(
+            e.message(),
+            "line 1: invalid number \"nan\""
+        );
+
+        let e = read_particles_path(
+            "/nonexistent/particles",
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(e.kind(), VoroErrorKind::File);
+    }
+
+    #[test]
+    fn import_test() {
+        let mut con = ContainerStd::new(
+            [0.0, 0.0, 0.0],
+            [2.0, 1.0, 1.0],
+            [2, 1, 1],
+            [false, false, false],
+        );
+        con.import_reader(
+            "0 0.5 0.5 0.5\n1 1.5 0.5 0.5\n".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(con.total_particles(), 2);
+        assert!(con
+            .import_reader("2 0.5\n".as_bytes())
+            .is_err());
+        // the import methods leave `Container0` usable as a trait object
+        let dyn_con: &mut dyn Container0 = &mut con;
+        assert_eq!(dyn_con.total_particles(), 2);
+
+        let mut con = ContainerRad::new(
+            [0.0, 0.0, 0.0],
+            [2.0, 1.0, 1.0],
+            [2, 1, 1],
+            [false, false, false],
+        );
+        con.put_all(&[
+            (0, [0.5, 0.5, 0.5], 0.1),
+            (1, [1.5, 0.5, 0.5], 0.2),
+        ]);
+        assert_eq!(con.total_particles(), 2);
+
+        let mut pre = PreContainerRad::new(
+            [0.0, 0.0, 0.0],
+            [2.0, 1.0, 1.0],
+            [false, false, false],
+        );
+        PreContainerImport::<ContainerRad>::import_reader(
+            &mut pre,
+            "0 0.5 0.5 0.5 0.1\n1 1.5 0.5 0.5 0.2\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            PreContainer::<ContainerRad>::total_particles(
+                &mut pre
+            ),
+            2
+        );
+    }
+}
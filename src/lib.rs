@@ -4,29 +4,54 @@
 //!
 //! This binding has been tested on Windows, Linux and MacOS.
 
+pub mod batch;
 pub mod cell;
 pub mod container;
 pub mod container_loop;
+pub mod custom_format;
+pub mod draw;
+pub mod error;
+pub mod import;
+pub mod neighbor_image;
 pub mod particle_marker;
 pub mod pre_container;
+pub mod slice;
+pub mod tessellation;
+pub mod vtk;
 pub mod wall;
 pub mod wall_list;
 
 pub mod prelude {
+    pub use crate::batch::{CellBatch, CellProperties};
     pub use crate::cell::{
-        VoroCell, VoroCellNbr, VoroCellSgl,
+        Face, VoroCell, VoroCellNbr, VoroCellSgl,
     };
     pub use crate::container::{
-        Container, Container0, Container1, Container2,
+        CellRecord, Container, Container0, Container1,
+        Container2, ContainerPeriodic,
+        ContainerPeriodicRad, ContainerPeriodicStd,
         ContainerRad, ContainerStd,
     };
     pub use crate::container_loop::{
-        ContainerLoop, LoopAll, LoopMarked, LoopSubset,
+        ContainerLoop, LoopAll, LoopAllPeriodic,
+        LoopMarked, LoopMarkedPeriodic, LoopSubset,
     };
+    pub use crate::custom_format::CustomFormat;
+    pub use crate::error::{VoroError, VoroErrorKind};
+    pub use crate::import::ContainerImport;
+    pub use crate::neighbor_image::NeighborImages;
     pub use crate::particle_marker::ParticleMarker;
+    pub use crate::slice::{
+        Plane, Polygon, Section, Slice,
+    };
+    pub use crate::tessellation::{
+        Tessellation, TessellationCell,
+    };
+    pub use crate::vtk::{Vtu, VtuFormat};
     pub use crate::wall::{
-        Wall, Wall0, Wall1, WallCone, WallCylinder,
-        WallPlane, WallSphere,
+        Wall, Wall0, Wall1, WallBox, WallCone, WallCustom,
+        WallCylinder, WallEllipsoid, WallPlane,
+        WallPolyhedron, WallSphere, WallTorus,
     };
     pub use crate::wall_list::{
         WallList, Walls, Walls0, Walls1, Walls2, Walls3,
@@ -0,0 +1,429 @@
+//! Periodic image shifts of the neighbors of a Voronoi cell.
+
+use crate::cell::{VoroCell, VoroCellNbr};
+use crate::container::{
+    ContainerPeriodicRad, ContainerPeriodicStd,
+    ContainerRad, ContainerStd,
+};
+use crate::container_loop::{
+    ContainerLoop, LoopAll, LoopAllPeriodic,
+};
+use std::collections::HashMap;
+
+type DVec3 = [f64; 3];
+type IVec3 = [i32; 3];
+type BVec3 = [bool; 3];
+
+/// The periodic lattice of a container.
+///
+/// The lattice vectors are (bx, 0, 0), (bxy, by, 0) and (bxz, byz, bz),
+/// following the convention of voro++. A rectangular container has no
+/// shear, and may be periodic in some directions only.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Domain {
+    size: DVec3,
+    shear: DVec3,
+    is_periodic: BVec3,
+}
+
+impl Domain {
+    pub(crate) fn rectangular(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        is_periodic: BVec3,
+    ) -> Self {
+        Self {
+            size: [
+                xyz_max[0] - xyz_min[0],
+                xyz_max[1] - xyz_min[1],
+                xyz_max[2] - xyz_min[2],
+            ],
+            shear: [0.0, 0.0, 0.0],
+            is_periodic,
+        }
+    }
+
+    pub(crate) fn triclinic(
+        box_size: DVec3,
+        box_shear: DVec3,
+    ) -> Self {
+        Self {
+            size: box_size,
+            shear: box_shear,
+            is_periodic: [true, true, true],
+        }
+    }
+
+    /// Finds the lattice vector closest to `delta`.
+    ///
+    /// Return the integer coefficients of the lattice vector, and the
+    /// distance between the lattice vector and `delta`.
+    fn nearest_shift(&self, delta: DVec3) -> (IVec3, f64) {
+        let [bx, by, bz] = self.size;
+        let [bxy, bxz, byz] = self.shear;
+        let mut d = delta;
+
+        // the lattice is lower-triangular, so solve from z to x.
+        let k = if self.is_periodic[2] {
+            (d[2] / bz).round()
+        } else {
+            0.0
+        };
+        d[0] -= k * bxz;
+        d[1] -= k * byz;
+        d[2] -= k * bz;
+
+        let j = if self.is_periodic[1] {
+            (d[1] / by).round()
+        } else {
+            0.0
+        };
+        d[0] -= j * bxy;
+        d[1] -= j * by;
+
+        let i = if self.is_periodic[0] {
+            (d[0] / bx).round()
+        } else {
+            0.0
+        };
+        d[0] -= i * bx;
+
+        let residual =
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2])
+                .sqrt();
+        ([i as i32, j as i32, k as i32], residual)
+    }
+}
+
+/// A helper for finding which periodic image of a neighbor each face of
+/// a Voronoi cell belongs to.
+///
+/// `VoroCellNbr::neighbors` only reports the id of the neighbor behind
+/// each face. In a periodic container, the same particle may appear
+/// behind several faces of a cell as different periodic images, and
+/// the minimum-image convention is unreliable in small or highly sheared
+/// domains. This struct recovers the image exactly from the geometry of
+/// each face.
+///
+/// It keeps a snapshot of the particle positions and radii taken when it
+/// is created. Particles put into the container afterward are unknown to
+/// it. Particle ids are assumed to be unique within the container.
+pub struct NeighborImages {
+    particles: HashMap<i32, (DVec3, f64)>,
+    domain: Domain,
+    is_radical: bool,
+}
+
+impl NeighborImages {
+    /// Takes a snapshot of the particles in a `ContainerStd`.
+    pub fn of_container_std(
+        container: &ContainerStd,
+    ) -> Self {
+        let domain = container.domain;
+        let mut cl = LoopAll::of_container_std(container);
+        Self {
+            particles: collect(&mut cl),
+            domain,
+            is_radical: false,
+        }
+    }
+
+    /// Takes a snapshot of the particles in a `ContainerRad`.
+    pub fn of_container_rad(
+        container: &ContainerRad,
+    ) -> Self {
+        let domain = container.domain;
+        let mut cl = LoopAll::of_container_rad(container);
+        Self {
+            particles: collect(&mut cl),
+            domain,
+            is_radical: true,
+        }
+    }
+
+    /// Takes a snapshot of the particles in a `ContainerPeriodicStd`.
+    pub fn of_container_periodic_std(
+        container: &ContainerPeriodicStd,
+    ) -> Self {
+        let domain = container.domain;
+        let mut cl =
+            LoopAllPeriodic::of_container_std(container);
+        Self {
+            particles: collect(&mut cl),
+            domain,
+            is_radical: false,
+        }
+    }
+
+    /// Takes a snapshot of the particles in a `ContainerPeriodicRad`.
+    pub fn of_container_periodic_rad(
+        container: &ContainerPeriodicRad,
+    ) -> Self {
+        let domain = container.domain;
+        let mut cl =
+            LoopAllPeriodic::of_container_rad(container);
+        Self {
+            particles: collect(&mut cl),
+            domain,
+            is_radical: true,
+        }
+    }
+
+    /// Computes the neighbor id and the periodic image shift of each
+    /// face of a Voronoi cell.
+    ///
+    /// * `id`: the id of the particle that the cell belongs to.
+    /// * `cell`: the Voronoi cell of the particle, as computed by the
+    /// container.
+    ///
+    /// Return a list of `(neighbor id, shift)` pairs in the same order as
+    /// `VoroCellNbr::neighbors`, or `None` if the particle is not in the
+    /// snapshot. The neighbor image is located at the stored position of
+    /// the neighbor plus `shift[0] * a + shift[1] * b + shift[2] * c`,
+    /// where a, b and c are the lattice vectors of the container. For a
+    /// rectangular container these are the side lengths along each axis.
+    /// Faces created by walls or non-periodic boundaries have a zero
+    /// shift.
+    pub fn shifts(
+        &self,
+        id: i32,
+        cell: &mut VoroCellNbr,
+    ) -> Option<Vec<(i32, IVec3)>> {
+        let &(xyz, r) = self.particles.get(&id)?;
+        let neighbors = cell.neighbors();
+        let normals = cell.normals();
+        let vertices = cell.vertices_local();
+        let face_vertices = cell.face_vertices();
+
+        let mut shifts =
+            Vec::with_capacity(neighbors.len());
+        let mut k = 0;
+        for (f, &j) in neighbors.iter().enumerate() {
+            let m = face_vertices[k] as usize;
+            let face = &face_vertices[k + 1..k + 1 + m];
+            k += m + 1;
+
+            let n = [
+                normals[3 * f],
+                normals[3 * f + 1],
+                normals[3 * f + 2],
+            ];
+            // signed distance from the particle to the face plane,
+            // averaged over the face vertices.
+            let d = face
+                .iter()
+                .map(|&v| {
+                    let v = 3 * v as usize;
+                    n[0] * vertices[v]
+                        + n[1] * vertices[v + 1]
+                        + n[2] * vertices[v + 2]
+                })
+                .sum::<f64>()
+                / m as f64;
+
+            let shift = match self.particles.get(&j) {
+                Some(&(xyz_j, r_j)) if j >= 0 => self
+                    .image_shift(xyz, r, xyz_j, r_j, n, d),
+                _ => [0, 0, 0],
+            };
+            shifts.push((j, shift));
+        }
+        Some(shifts)
+    }
+
+    fn image_shift(
+        &self,
+        xyz: DVec3,
+        r: f64,
+        xyz_j: DVec3,
+        r_j: f64,
+        n: DVec3,
+        d: f64,
+    ) -> IVec3 {
+        // distance between the particle and the neighbor image. For a
+        // radical tessellation, the face sits at distance
+        // d = (l^2 + r^2 - r_j^2) / (2l) from the particle, which has two
+        // roots in l; take the one that lands on a lattice point.
+        let candidates = if self.is_radical {
+            let s =
+                (d * d - r * r + r_j * r_j).max(0.0).sqrt();
+            vec![d + s, d - s]
+        } else {
+            vec![2.0 * d]
+        };
+
+        let mut best = ([0, 0, 0], f64::INFINITY);
+        for l in candidates {
+            let delta = [
+                xyz[0] + l * n[0] - xyz_j[0],
+                xyz[1] + l * n[1] - xyz_j[1],
+                xyz[2] + l * n[2] - xyz_j[2],
+            ];
+            let (shift, residual) =
+                self.domain.nearest_shift(delta);
+            if residual < best.1 {
+                best = (shift, residual);
+            }
+        }
+        best.0
+    }
+}
+
+fn collect<T: ContainerLoop>(
+    cl: &mut T,
+) -> HashMap<i32, (DVec3, f64)> {
+    let mut particles = HashMap::new();
+    if cl.start() {
+        loop {
+            particles.insert(
+                cl.particle_id(),
+                (cl.position(), cl.radius()),
+            );
+            if !cl.inc() {
+                break;
+            }
+        }
+    }
+    particles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Container0, Container2};
+
+    fn sorted(
+        mut v: Vec<(i32, IVec3)>,
+    ) -> Vec<(i32, IVec3)> {
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn container_std_test() {
+        let mut con = ContainerStd::new(
+            [0.0, 0.0, 0.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [true, true, true],
+        );
+        con.put(0, [1.0, 5.0, 5.0], 0.0);
+        con.put(1, [9.0, 5.0, 5.0], 0.0);
+
+        let images = NeighborImages::of_container_std(&con);
+        let mut cl = LoopAll::of_container_std(&con);
+        assert!(cl.start());
+        let mut cell: VoroCellNbr =
+            con.compute_cell(&mut cl).unwrap();
+        assert_eq!(cl.particle_id(), 0);
+        assert!((cell.volume() - 500.0).abs() < 1e-8);
+
+        let shifts = images.shifts(0, &mut cell).unwrap();
+        assert_eq!(
+            sorted(shifts),
+            vec![
+                (0, [0, -1, 0]),
+                (0, [0, 0, -1]),
+                (0, [0, 0, 1]),
+                (0, [0, 1, 0]),
+                (1, [-1, 0, 0]),
+                (1, [0, 0, 0]),
+            ]
+        );
+        assert!(images.shifts(2, &mut cell).is_none());
+    }
+
+    #[test]
+    fn container_std_wall_test() {
+        let mut con = ContainerStd::new(
+            [0.0, 0.0, 0.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [true, false, false],
+        );
+        con.put(0, [1.0, 5.0, 5.0], 0.0);
+        con.put(1, [9.0, 5.0, 5.0], 0.0);
+
+        let images = NeighborImages::of_container_std(&con);
+        let mut cl = LoopAll::of_container_std(&con);
+        assert!(cl.start());
+        let mut cell: VoroCellNbr =
+            con.compute_cell(&mut cl).unwrap();
+        assert_eq!(cl.particle_id(), 0);
+        let shifts = images.shifts(0, &mut cell).unwrap();
+        assert_eq!(shifts.len(), 6);
+        for (j, shift) in shifts {
+            match j {
+                1 => assert!(
+                    shift == [-1, 0, 0]
+                        || shift == [0, 0, 0]
+                ),
+                _ => {
+                    assert!(j < 0);
+                    assert_eq!(shift, [0, 0, 0]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn container_rad_test() {
+        let mut con = ContainerRad::new(
+            [0.0, 0.0, 0.0],
+            [10.0, 10.0, 10.0],
+            [2, 2, 2],
+            [true, true, true],
+        );
+        con.put(0, [1.0, 5.0, 5.0], 0.5);
+        con.put(1, [9.0, 5.0, 5.0], 1.5);
+
+        let images = NeighborImages::of_container_rad(&con);
+        let mut cl = LoopAll::of_container_rad(&con);
+        assert!(cl.start());
+        let mut cell: VoroCellNbr =
+            con.compute_cell(&mut cl).unwrap();
+        assert_eq!(cl.particle_id(), 0);
+        let shifts = images.shifts(0, &mut cell).unwrap();
+        assert_eq!(
+            sorted(shifts),
+            vec![
+                (0, [0, -1, 0]),
+                (0, [0, 0, -1]),
+                (0, [0, 0, 1]),
+                (0, [0, 1, 0]),
+                (1, [-1, 0, 0]),
+                (1, [0, 0, 0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn container_periodic_test() {
+        let mut con = ContainerPeriodicStd::new(
+            [4.0, 4.0, 4.0],
+            [1.5, -1.0, 2.5],
+            [2, 2, 2],
+        );
+        con.put(0, [1.0, 1.0, 1.0], 0.0);
+
+        let images =
+            NeighborImages::of_container_periodic_std(&con);
+        let mut cl =
+            LoopAllPeriodic::of_container_std(&con);
+        assert!(cl.start());
+        let mut cell: VoroCellNbr =
+            con.compute_cell(&mut cl).unwrap();
+        assert!((cell.volume() - 64.0).abs() < 1e-8);
+
+        // a single particle only neighbors its own images, and every
+        // image appears together with its mirror.
+        let shifts = images.shifts(0, &mut cell).unwrap();
+        assert!(!shifts.is_empty());
+        for &(j, s) in shifts.iter() {
+            assert_eq!(j, 0);
+            assert_ne!(s, [0, 0, 0]);
+            assert!(shifts
+                .contains(&(0, [-s[0], -s[1], -s[2]])));
+        }
+    }
+}
@@ -13,7 +13,10 @@ pub mod ffi {
     }
 }
 
+use crate::container::pin_shared;
+use crate::error::{check_memory, VoroError};
 use cxx::UniquePtr;
+use std::pin::Pin;
 
 /// A class for storing ordering information when particles are added to
 /// a container.
@@ -31,6 +34,10 @@ pub struct ParticleMarker {
     pub(crate) inner: UniquePtr<ffi::particle_order>,
 }
 
+// SAFETY: the ordering information is owned by this struct, and no
+// method reads it through `&self`.
+unsafe impl Send for ParticleMarker {}
+
 impl ParticleMarker {
     /// Create a new `ParticleMarker`.
     pub fn new() -> Self {
@@ -42,8 +49,30 @@ impl ParticleMarker {
     /// * `initial_memory`: the initial amount of memory to allocate,
     /// in terms of particle count.
     pub fn new_with_memory(initial_memory: i32) -> Self {
-        Self {
+        Self::try_new_with_memory(initial_memory)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_memory`.
+    ///
+    /// Return an error if `initial_memory` is not positive.
+    pub fn try_new_with_memory(
+        initial_memory: i32,
+    ) -> Result<Self, VoroError> {
+        check_memory(initial_memory)?;
+        Ok(Self {
             inner: ffi::new_particle_order(initial_memory),
-        }
+        })
+    }
+
+    /// Pins the C++ ordering information for a loop reading it, see
+    /// `pin_shared`.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn inner_pin(
+        &self,
+    ) -> Pin<&mut ffi::particle_order> {
+        // SAFETY: the marker is not `Sync`, and the returned reference is
+        // borrowed from `self`.
+        unsafe { pin_shared(self.inner.as_mut_ptr()) }
     }
 }
@@ -40,17 +40,17 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        );
+        ) -> Result<()>;
         fn setup(
             self: Pin<&mut pre_container>,
             con: Pin<&mut container>,
-        );
+        ) -> Result<()>;
         #[rust_name = "setup_with_particle_order"]
         fn setup(
             self: Pin<&mut pre_container>,
             vo: Pin<&mut particle_order>,
             con: Pin<&mut container>,
-        );
+        ) -> Result<()>;
 
         type pre_container_poly;
         #[rust_name = "new_pre_container_poly"]
@@ -81,20 +81,22 @@ pub mod ffi {
             y: f64,
             z: f64,
             r: f64,
-        );
+        ) -> Result<()>;
         fn setup(
             self: Pin<&mut pre_container_poly>,
             con: Pin<&mut container_poly>,
-        );
+        ) -> Result<()>;
         #[rust_name = "setup_with_particle_order"]
         fn setup(
             self: Pin<&mut pre_container_poly>,
             vo: Pin<&mut particle_order>,
             con: Pin<&mut container_poly>,
-        );
+        ) -> Result<()>;
     }
 }
 
+use crate::error::{check_box, VoroError};
+use crate::import::PreContainerImport;
 use crate::prelude::{
     ContainerRad, ContainerStd, ParticleMarker,
 };
@@ -110,6 +112,10 @@ pub struct PreContainerStd {
     pub(crate) inner: UniquePtr<ffi::pre_container>,
 }
 
+// SAFETY: the particles are owned by this struct, and no method reads
+// them through `&self`.
+unsafe impl Send for PreContainerStd {}
+
 impl PreContainerStd {
     /// The class constructor sets up the geometry of container,
     /// initializing the minimum and maximum coordinates in each
@@ -124,7 +130,21 @@ impl PreContainerStd {
         xyz_max: DVec3,
         is_periodic: BVec3,
     ) -> Self {
-        Self {
+        Self::try_new(xyz_min, xyz_max, is_periodic)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new`.
+    ///
+    /// Return an error if `xyz_min` and `xyz_max` do not span a non-empty
+    /// box.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        is_periodic: BVec3,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
+        Ok(Self {
             inner: ffi::new_pre_container(
                 xyz_min[0],
                 xyz_max[0],
@@ -136,7 +156,7 @@ impl PreContainerStd {
                 is_periodic[1],
                 is_periodic[2],
             ),
-        }
+        })
     }
 }
 
@@ -146,6 +166,10 @@ pub struct PreContainerRad {
     pub(crate) inner: UniquePtr<ffi::pre_container_poly>,
 }
 
+// SAFETY: the particles are owned by this struct, and no method reads
+// them through `&self`.
+unsafe impl Send for PreContainerRad {}
+
 impl PreContainerRad {
     /// The class constructor sets up the geometry of container,
     /// initializing the minimum and maximum coordinates in each
@@ -160,7 +184,21 @@ impl PreContainerRad {
         xyz_max: DVec3,
         is_periodic: BVec3,
     ) -> Self {
-        Self {
+        Self::try_new(xyz_min, xyz_max, is_periodic)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new`.
+    ///
+    /// Return an error if `xyz_min` and `xyz_max` do not span a non-empty
+    /// box.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        is_periodic: BVec3,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
+        Ok(Self {
             inner: ffi::new_pre_container_poly(
                 xyz_min[0],
                 xyz_max[0],
@@ -172,7 +210,7 @@ impl PreContainerRad {
                 is_periodic[1],
                 is_periodic[2],
             ),
-        }
+        })
     }
 }
 
@@ -193,23 +231,76 @@ pub trait PreContainer<T> {
     /// * `n`: the numerical ID of the inserted particle.
     /// * `xyz`: the position vector of the inserted particle.
     /// * `r`: the radius of the particle. This is ignored for `PreContainerStd`.
-    fn put(&mut self, n: i32, xyz: DVec3, r: f64);
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_put`.
+    fn put(&mut self, n: i32, xyz: DVec3, r: f64) {
+        self.try_put(n, xyz, r)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `put`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// the memory limit on the number of chunks is exceeded.
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError>;
+
+    /// Stores many particles.
+    ///
+    /// * `particles`: the ID, position and radius of each particle. The
+    ///   radius is ignored for `PreContainerStd`.
+    fn put_all(&mut self, particles: &[(i32, DVec3, f64)]) {
+        for &(n, xyz, r) in particles {
+            self.put(n, xyz, r);
+        }
+    }
 
     /// Transfers the particles stored within the class to a container class.
     ///
     /// * `container`: the container class to transfer to.
-    fn setup(&mut self, container: &mut T);
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_setup`.
+    fn setup(&mut self, container: &mut T) {
+        self.try_setup(container)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `setup`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// the memory limit of a grid block in the container is exceeded.
+    fn try_setup(
+        &mut self,
+        container: &mut T,
+    ) -> Result<(), VoroError>;
 
     /// Transfers the particles stored within the class to a container class, also
     /// recording the order in which particles were stored.
     ///
     /// * `marker`: the ordering class to use.
     /// * `container`: the container class to transfer to.
+    ///
+    /// Panics if voro++ reports a fatal error, see
+    /// `try_setup_with_marker`.
     fn setup_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         container: &mut T,
-    );
+    ) {
+        self.try_setup_with_marker(marker, container)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `setup_with_marker`.
+    fn try_setup_with_marker(
+        &mut self,
+        marker: &mut ParticleMarker,
+        container: &mut T,
+    ) -> Result<(), VoroError>;
 }
 
 impl<'a> PreContainer<ContainerStd<'a>>
@@ -229,25 +320,37 @@ impl<'a> PreContainer<ContainerStd<'a>>
         self.inner.pin_mut().total_particles()
     }
 
-    fn put(&mut self, n: i32, xyz: DVec3, _: f64) {
-        self.inner.pin_mut().put(n, xyz[0], xyz[1], xyz[2]);
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        _: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
+            .pin_mut()
+            .put(n, xyz[0], xyz[1], xyz[2])?)
     }
 
-    fn setup(&mut self, container: &mut ContainerStd) {
-        self.inner
+    fn try_setup(
+        &mut self,
+        container: &mut ContainerStd,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .setup(container.inner.pin_mut());
+            .setup(container.inner.pin_mut())?)
     }
 
-    fn setup_with_marker(
+    fn try_setup_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         container: &mut ContainerStd,
-    ) {
-        self.inner.pin_mut().setup_with_particle_order(
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().setup_with_particle_order(
             marker.inner.pin_mut(),
             container.inner.pin_mut(),
-        );
+        )?)
     }
 }
 
@@ -268,30 +371,52 @@ impl<'a> PreContainer<ContainerRad<'a>>
         self.inner.pin_mut().total_particles()
     }
 
-    fn put(&mut self, n: i32, xyz: DVec3, r: f64) {
-        self.inner
+    fn try_put(
+        &mut self,
+        n: i32,
+        xyz: DVec3,
+        r: f64,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .put(n, xyz[0], xyz[1], xyz[2], r);
+            .put(n, xyz[0], xyz[1], xyz[2], r)?)
     }
 
-    fn setup(&mut self, container: &mut ContainerRad) {
-        self.inner
+    fn try_setup(
+        &mut self,
+        container: &mut ContainerRad,
+    ) -> Result<(), VoroError> {
+        Ok(self
+            .inner
             .pin_mut()
-            .setup(container.inner.pin_mut());
+            .setup(container.inner.pin_mut())?)
     }
 
-    fn setup_with_marker(
+    fn try_setup_with_marker(
         &mut self,
         marker: &mut ParticleMarker,
         container: &mut ContainerRad,
-    ) {
-        self.inner.pin_mut().setup_with_particle_order(
+    ) -> Result<(), VoroError> {
+        Ok(self.inner.pin_mut().setup_with_particle_order(
             marker.inner.pin_mut(),
             container.inner.pin_mut(),
-        );
+        )?)
     }
 }
 
+impl<'a> PreContainerImport<ContainerStd<'a>>
+    for PreContainerStd
+{
+    const HAS_RADIUS: bool = false;
+}
+
+impl<'a> PreContainerImport<ContainerRad<'a>>
+    for PreContainerRad
+{
+    const HAS_RADIUS: bool = true;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,4 +460,20 @@ mod tests {
         assert!(c.is_some());
         assert_eq!(c.unwrap().0, 8);
     }
+
+    #[test]
+    fn invalid_argument_test() {
+        let pc = PreContainerStd::try_new(
+            [10.0, -10.0, -10.0],
+            [-10.0, 10.0, 10.0],
+            [false, false, false],
+        );
+        assert!(pc.is_err());
+        let pc = PreContainerRad::try_new(
+            [-10.0, -10.0, f64::NAN],
+            [10.0, 10.0, 10.0],
+            [false, false, false],
+        );
+        assert!(pc.is_err());
+    }
 }
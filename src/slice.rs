@@ -0,0 +1,432 @@
+//! Cross sections of Voronoi cells by a plane.
+
+use crate::cell::VoroCell;
+use crate::container::CellRecord;
+use crate::error::VoroError;
+use std::fmt::Write;
+
+type DVec3 = [f64; 3];
+
+/// A plane, given as the points `x` with `normal.x = offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    /// The normal vector of the plane.
+    pub normal: DVec3,
+    /// The offset of the plane along its normal, in units of the length
+    /// of `normal`.
+    pub offset: f64,
+}
+
+impl Plane {
+    /// Creates the plane `normal.x = offset`.
+    ///
+    /// * `normal`: the normal vector of the plane.
+    /// * `offset`: the offset of the plane along its normal.
+    pub fn new(normal: DVec3, offset: f64) -> Self {
+        Self { normal, offset }
+    }
+
+    /// Creates the plane through a point.
+    ///
+    /// * `point`: a point on the plane.
+    /// * `normal`: the normal vector of the plane.
+    pub fn through(point: DVec3, normal: DVec3) -> Self {
+        Self {
+            normal,
+            offset: dot(normal, point),
+        }
+    }
+
+    /// Returns the plane moved by `-xyz`, which is the same plane in the
+    /// coordinate system whose origin is at `xyz`.
+    pub fn relative_to(&self, xyz: DVec3) -> Self {
+        Self {
+            normal: self.normal,
+            offset: self.offset - dot(self.normal, xyz),
+        }
+    }
+
+    /// Returns the unit vectors `u` and `v` spanning the plane, such that
+    /// `u`, `v` and the normal form a right-handed frame.
+    pub fn basis(&self) -> (DVec3, DVec3) {
+        let n = unit(self.normal);
+        let a = if n[0].abs() < 0.9 {
+            [1.0, 0.0, 0.0]
+        } else {
+            [0.0, 1.0, 0.0]
+        };
+        let u = unit(cross(a, n));
+        (u, cross(n, u))
+    }
+
+    /// Returns the coordinates of a point in the frame of `basis`.
+    ///
+    /// * `xyz`: the point to project.
+    pub fn project(&self, xyz: DVec3) -> [f64; 2] {
+        let (u, v) = self.basis();
+        [dot(u, xyz), dot(v, xyz)]
+    }
+}
+
+/// A convex polygon in space.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+    /// The vertices of the polygon, ordered counterclockwise when seen
+    /// from the side that the normal of its plane points to.
+    pub vertices: Vec<DVec3>,
+}
+
+impl Polygon {
+    /// Returns the area of the polygon.
+    pub fn area(&self) -> f64 {
+        let mut s = [0.0; 3];
+        for (i, a) in self.vertices.iter().enumerate() {
+            let b = self.vertices
+                [(i + 1) % self.vertices.len()];
+            let c = cross(*a, b);
+            s = [s[0] + c[0], s[1] + c[1], s[2] + c[2]];
+        }
+        0.5 * dot(s, s).sqrt()
+    }
+
+    /// Returns the polygon translated by a vector.
+    ///
+    /// * `xyz`: the vector to translate by.
+    pub fn translated(&self, xyz: DVec3) -> Self {
+        Self {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|v| {
+                    [
+                        v[0] + xyz[0],
+                        v[1] + xyz[1],
+                        v[2] + xyz[2],
+                    ]
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the cross section of a computed cell by a plane in the global
+/// coordinate system, if the cell crosses it.
+fn section_of<T: VoroCell>(
+    mut record: CellRecord<T>,
+    plane: Plane,
+) -> Option<Section> {
+    let local = plane.relative_to(record.position);
+    let polygon = slice_cell(&mut record.cell, local)?;
+    Some(Section {
+        id: record.id,
+        position: record.position,
+        polygon: polygon.translated(record.position),
+    })
+}
+
+/// Intersects a cell with a plane, both in the local coordinate system
+/// of the cell.
+pub(crate) fn slice_cell<T: VoroCell + ?Sized>(
+    cell: &mut T,
+    plane: Plane,
+) -> Option<Polygon> {
+    let n = plane.normal;
+    let a = plane.offset;
+    if !cell.plane_intersects(n, 2.0 * a)
+        || !cell.plane_intersects(
+            [-n[0], -n[1], -n[2]],
+            -2.0 * a,
+        )
+    {
+        return None;
+    }
+
+    let vertices = cell.vertices();
+    let eps = 1e-10 * dot(n, n).sqrt();
+    let side: Vec<f64> =
+        vertices.iter().map(|v| dot(n, *v) - a).collect();
+    let mut points: Vec<DVec3> = vertices
+        .iter()
+        .zip(&side)
+        .filter(|(_, s)| s.abs() <= eps)
+        .map(|(v, _)| *v)
+        .collect();
+    for (i, j) in cell.edges() {
+        let (si, sj) = (side[i], side[j]);
+        if (si < -eps && sj > eps)
+            || (si > eps && sj < -eps)
+        {
+            let t = si / (si - sj);
+            let (p, q) = (vertices[i], vertices[j]);
+            points.push([
+                p[0] + t * (q[0] - p[0]),
+                p[1] + t * (q[1] - p[1]),
+                p[2] + t * (q[2] - p[2]),
+            ]);
+        }
+    }
+    if points.len() < 3 {
+        return None;
+    }
+
+    let k = 1.0 / points.len() as f64;
+    let c = points.iter().fold([0.0; 3], |s, p| {
+        [s[0] + k * p[0], s[1] + k * p[1], s[2] + k * p[2]]
+    });
+    let (u, v) = plane.basis();
+    let angle = |p: &DVec3| {
+        let d = [p[0] - c[0], p[1] - c[1], p[2] - c[2]];
+        dot(v, d).atan2(dot(u, d))
+    };
+    points.sort_by(|p, q| angle(p).total_cmp(&angle(q)));
+    points.dedup_by(|p, q| {
+        let d = [p[0] - q[0], p[1] - q[1], p[2] - q[2]];
+        dot(d, d) <= eps * eps
+    });
+    if points.len() < 3 {
+        return None;
+    }
+    Some(Polygon { vertices: points })
+}
+
+/// The cross section of the cell of a particle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section {
+    /// The ID of the particle.
+    pub id: i32,
+    /// The position of the particle.
+    pub position: DVec3,
+    /// The cross section in the global coordinate system.
+    pub polygon: Polygon,
+}
+
+/// The cross sections of the cells of a container by a plane.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slice {
+    /// The plane of the cross sections.
+    pub plane: Plane,
+    /// The cross sections, sorted by particle ID.
+    pub sections: Vec<Section>,
+}
+
+impl Slice {
+    /// Slices computed cells, such as the ones returned by the `cells`
+    /// method of the containers. Cells that do not cross the plane are
+    /// skipped.
+    ///
+    /// * `cells`: the cells to slice.
+    /// * `plane`: the plane in the global coordinate system.
+    pub fn from_cells<T, I>(cells: I, plane: Plane) -> Self
+    where
+        T: VoroCell,
+        I: IntoIterator<Item = CellRecord<T>>,
+    {
+        let mut sections: Vec<Section> = cells
+            .into_iter()
+            .filter_map(|record| section_of(record, plane))
+            .collect();
+        sections.sort_by_key(|s| s.id);
+        Self { plane, sections }
+    }
+
+    /// Fallible version of `from_cells`, for the cells returned by the
+    /// `try_cells` method of the containers.
+    ///
+    /// Return the first error among the cells.
+    pub fn try_from_cells<T, I>(
+        cells: I,
+        plane: Plane,
+    ) -> Result<Self, VoroError>
+    where
+        T: VoroCell,
+        I: IntoIterator<
+            Item = Result<CellRecord<T>, VoroError>,
+        >,
+    {
+        let mut sections = Vec::new();
+        for record in cells {
+            sections.extend(section_of(record?, plane));
+        }
+        sections.sort_by_key(|s| s.id);
+        Ok(Self { plane, sections })
+    }
+
+    /// Renders the cross sections as an SVG image in the frame of
+    /// `Plane::basis`, colored by particle ID.
+    pub fn to_svg(&self) -> String {
+        self.render(|s| {
+            let h = (s.id as f64 * 0.618_033_988_75)
+                .rem_euclid(1.0);
+            format!("hsl({:.0},60%,65%)", 360.0 * h)
+        })
+    }
+
+    /// Renders the cross sections as an SVG image in the frame of
+    /// `Plane::basis`, colored from blue to red by a scalar.
+    ///
+    /// * `scalar`: the value of each cross section.
+    pub fn to_svg_with<F>(&self, scalar: F) -> String
+    where
+        F: Fn(&Section) -> f64,
+    {
+        let values: Vec<f64> =
+            self.sections.iter().map(&scalar).collect();
+        let min = values
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let max = values
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let range = if max > min { max - min } else { 1.0 };
+        self.render(|s| {
+            let t =
+                ((scalar(s) - min) / range).clamp(0.0, 1.0);
+            format!("hsl({:.0},70%,50%)", 240.0 * (1.0 - t))
+        })
+    }
+
+    fn render<F>(&self, fill: F) -> String
+    where
+        F: Fn(&Section) -> String,
+    {
+        let polygons: Vec<Vec<[f64; 2]>> = self
+            .sections
+            .iter()
+            .map(|s| {
+                s.polygon
+                    .vertices
+                    .iter()
+                    .map(|v| {
+                        let p = self.plane.project(*v);
+                        // SVG's y axis points down.
+                        [p[0], -p[1]]
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut min = [f64::INFINITY; 2];
+        let mut max = [f64::NEG_INFINITY; 2];
+        for p in polygons.iter().flatten() {
+            for i in 0..2 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+        if polygons.iter().all(|p| p.is_empty()) {
+            min = [0.0; 2];
+            max = [1.0; 2];
+        }
+        let w = (max[0] - min[0]).max(f64::MIN_POSITIVE);
+        let h = (max[1] - min[1]).max(f64::MIN_POSITIVE);
+        let stroke = 2e-3 * w.max(h);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             viewBox=\"{} {} {} {}\">",
+            min[0], min[1], w, h
+        );
+        for (s, p) in self.sections.iter().zip(&polygons) {
+            let points: Vec<String> = p
+                .iter()
+                .map(|p| format!("{},{}", p[0], p[1]))
+                .collect();
+            let _ = writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{}\" stroke=\"black\" \
+                 stroke-width=\"{}\"><title>{}</title></polygon>",
+                points.join(" "),
+                fill(s),
+                stroke,
+                s.id
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn dot(a: DVec3, b: DVec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: DVec3, b: DVec3) -> DVec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn unit(a: DVec3) -> DVec3 {
+    let l = dot(a, a).sqrt();
+    [a[0] / l, a[1] / l, a[2] / l]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn cell_slice_test() {
+        let mut c0 = VoroCellSgl::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let p = c0
+            .slice(Plane::new([0.0, 0.0, 1.0], 0.5))
+            .unwrap();
+        assert_eq!(p.vertices.len(), 4);
+        assert!((p.area() - 4.0).abs() < 1e-10);
+        assert!(p
+            .vertices
+            .iter()
+            .all(|v| (v[2] - 0.5).abs() < 1e-10));
+
+        let p = c0
+            .slice(Plane::new([1.0, 1.0, 1.0], 0.0))
+            .unwrap();
+        assert_eq!(p.vertices.len(), 6);
+
+        assert!(c0
+            .slice(Plane::new([0.0, 0.0, 1.0], 2.0))
+            .is_none());
+    }
+
+    #[test]
+    fn container_slice_test() {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, -0.5], 0.0);
+        con.put(1, [0.5, 0.0, -0.5], 0.0);
+        con.put(2, [0.0, 0.0, 0.5], 0.0);
+
+        let slice =
+            con.slice(Plane::new([0.0, 0.0, 1.0], -0.5));
+        let ids: Vec<i32> =
+            slice.sections.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![0, 1]);
+        let area: f64 = slice
+            .sections
+            .iter()
+            .map(|s| s.polygon.area())
+            .sum();
+        assert!((area - 4.0).abs() < 1e-10);
+
+        let svg = slice.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polygon").count(), 2);
+        let svg = slice.to_svg_with(|s| {
+            s.polygon.area() + s.id as f64
+        });
+        assert!(svg.contains("hsl(240,"));
+        assert!(svg.contains("hsl(0,"));
+    }
+}
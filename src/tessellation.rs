@@ -0,0 +1,240 @@
+//! Owned Voronoi tessellations.
+
+use crate::cell::{Face, VoroCell};
+use crate::container::CellRecord;
+use crate::error::VoroError;
+
+type DVec3 = [f64; 3];
+
+/// The Voronoi cell of a particle in a `Tessellation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TessellationCell {
+    /// The ID of the particle.
+    pub id: i32,
+    /// The position of the particle.
+    pub position: DVec3,
+    /// The radius of the particle.
+    pub radius: f64,
+    /// The volume of the cell.
+    pub volume: f64,
+    /// The centroid of the cell in the global coordinate system.
+    pub centroid: DVec3,
+    /// The vertices of the cell in the global coordinate system.
+    pub vertices: Vec<DVec3>,
+    /// The faces of the cell.
+    pub faces: Vec<Face>,
+}
+
+impl TessellationCell {
+    /// Extracts the geometry of a computed cell.
+    pub fn from_record<T: VoroCell>(
+        mut record: CellRecord<T>,
+    ) -> Self {
+        let cell = &mut record.cell;
+        let p = record.position;
+        let c = cell.centroid();
+        let vertices = cell
+            .vertices()
+            .into_iter()
+            .map(|v| {
+                [p[0] + v[0], p[1] + v[1], p[2] + v[2]]
+            })
+            .collect();
+
+        Self {
+            id: record.id,
+            position: p,
+            radius: record.radius,
+            volume: cell.volume(),
+            centroid: [
+                p[0] + c[0],
+                p[1] + c[1],
+                p[2] + c[2],
+            ],
+            vertices,
+            faces: cell.faces(),
+        }
+    }
+
+    /// Returns the positions of the vertices of a face.
+    ///
+    /// * `face`: the index of the face.
+    pub fn face_positions(
+        &self,
+        face: usize,
+    ) -> impl Iterator<Item = DVec3> + '_ {
+        self.faces[face]
+            .vertices
+            .iter()
+            .map(move |&v| self.vertices[v])
+    }
+
+    /// Returns the IDs of the neighbors of the cell, if the cells were
+    /// computed with neighbor tracking.
+    pub fn neighbors(
+        &self,
+    ) -> impl Iterator<Item = i32> + '_ {
+        self.faces.iter().filter_map(|f| f.neighbor)
+    }
+}
+
+/// The Voronoi cells of all the particles of a container, stored as plain
+/// Rust data.
+///
+/// A tessellation does not refer to the container or the cells that it
+/// was computed from, so it can be kept after they are dropped. The cells
+/// are sorted by particle ID.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tessellation {
+    cells: Vec<TessellationCell>,
+}
+
+impl Tessellation {
+    /// Builds a tessellation from computed cells, such as the ones
+    /// returned by the `cells` method of the containers.
+    pub fn from_cells<T, I>(cells: I) -> Self
+    where
+        T: VoroCell,
+        I: IntoIterator<Item = CellRecord<T>>,
+    {
+        let mut cells: Vec<_> = cells
+            .into_iter()
+            .map(TessellationCell::from_record)
+            .collect();
+        cells.sort_by_key(|c| c.id);
+        Self { cells }
+    }
+
+    /// Fallible version of `from_cells`, for the cells returned by the
+    /// `try_cells` method of the containers.
+    ///
+    /// Return the first error among the cells.
+    pub fn try_from_cells<T, I>(
+        cells: I,
+    ) -> Result<Self, VoroError>
+    where
+        T: VoroCell,
+        I: IntoIterator<
+            Item = Result<CellRecord<T>, VoroError>,
+        >,
+    {
+        let mut cells = cells
+            .into_iter()
+            .map(|r| r.map(TessellationCell::from_record))
+            .collect::<Result<Vec<_>, _>>()?;
+        cells.sort_by_key(|c| c.id);
+        Ok(Self { cells })
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns true if there are no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the cells, sorted by particle ID.
+    pub fn cells(&self) -> &[TessellationCell] {
+        &self.cells
+    }
+
+    /// Returns an iterator over the cells, sorted by particle ID.
+    pub fn iter(
+        &self,
+    ) -> std::slice::Iter<'_, TessellationCell> {
+        self.cells.iter()
+    }
+
+    /// Returns the cell of a particle, or `None` if the particle has no
+    /// cell.
+    ///
+    /// * `id`: the ID of the particle.
+    pub fn get(
+        &self,
+        id: i32,
+    ) -> Option<&TessellationCell> {
+        self.cells
+            .binary_search_by_key(&id, |c| c.id)
+            .ok()
+            .map(|i| &self.cells[i])
+    }
+
+    /// Returns the sum of the volumes of the cells.
+    pub fn volume(&self) -> f64 {
+        self.cells.iter().map(|c| c.volume).sum()
+    }
+}
+
+impl<'a> IntoIterator for &'a Tessellation {
+    type Item = &'a TessellationCell;
+    type IntoIter = std::slice::Iter<'a, TessellationCell>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn tessellate_test() {
+        let tess = {
+            let mut con = ContainerStd::new(
+                [-1.0, -1.0, -1.0],
+                [1.0, 1.0, 1.0],
+                [2, 2, 2],
+                [false, false, false],
+            );
+            con.put(1, [0.5, 0.0, 0.0], 0.0);
+            con.put(0, [-0.5, 0.0, 0.0], 0.0);
+            con.tessellate::<VoroCellNbr>()
+        };
+        assert_eq!(tess.len(), 2);
+        assert!((tess.volume() - 8.0).abs() < 1e-10);
+
+        let cell = tess.get(0).unwrap();
+        assert_eq!(cell.id, 0);
+        assert_eq!(cell.vertices.len(), 8);
+        assert_eq!(cell.faces.len(), 6);
+        assert!((cell.centroid[0] + 0.5).abs() < 1e-10);
+        assert_eq!(
+            cell.neighbors().filter(|&n| n == 1).count(),
+            1
+        );
+        let areas: f64 =
+            cell.faces.iter().map(|f| f.area).sum();
+        assert!((areas - 16.0).abs() < 1e-10);
+        let i = cell
+            .faces
+            .iter()
+            .position(|f| f.neighbor == Some(1))
+            .unwrap();
+        assert_eq!(cell.faces[i].vertices.len(), 4);
+        assert!((cell.faces[i].area - 4.0).abs() < 1e-10);
+        assert!(
+            (cell.faces[i].normal[0] - 1.0).abs() < 1e-10
+        );
+        for v in cell.face_positions(i) {
+            assert!(v[0].abs() < 1e-10);
+        }
+        assert!(tess.get(2).is_none());
+
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [0.0, 0.0, 0.0], 0.0);
+        let tess = con.tessellate::<VoroCellSgl>();
+        assert!(tess.cells()[0]
+            .faces
+            .iter()
+            .all(|f| f.neighbor.is_none()));
+    }
+}
@@ -0,0 +1,39 @@
+// Compiles voro++ with its fatal errors turned into C++ exceptions.
+//
+// voro++ reports fatal errors through voro_fatal_error(p, status), which
+// prints the message p and calls exit(status). Redirecting exit() to a
+// throw lets the Rust side catch the error through cxx instead of losing
+// the whole process. The exception message is "status:p", which
+// src/error.rs decodes.
+//
+// voro_fatal_error() is the only caller of exit() in voro++, and the
+// macro refers to its parameter p. Any other caller would fail to
+// compile, rather than silently lose its message.
+//
+// The standard headers are included first so that the macro only
+// touches voro++ itself. This file is compiled with -ansi, so it must
+// stay valid C++98.
+
+#include <algorithm>
+#include <cmath>
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <limits>
+#include <sstream>
+#include <stdexcept>
+#include <vector>
+
+namespace voro
+{
+    inline void throw_fatal_error(const char *p, int status)
+    {
+        std::ostringstream message;
+        message << status << ':' << p;
+        throw std::runtime_error(message.str());
+    }
+}
+
+#define exit(status) ::voro::throw_fatal_error(p, status)
+#include "../voro/src/voro++.cc"
+#undef exit
@@ -0,0 +1,432 @@
+//! Export of tessellations to VTK's XML unstructured grid format (VTU).
+
+use crate::error::{VoroError, VoroErrorKind};
+use crate::tessellation::Tessellation;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// The encoding of the data arrays in a VTU file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VtuFormat {
+    /// The arrays are written as text inside the XML.
+    #[default]
+    Ascii,
+    /// The arrays are written as raw little-endian bytes in an appended
+    /// data section after the XML.
+    Appended,
+}
+
+enum Data {
+    F64(Vec<f64>),
+    I64(Vec<i64>),
+    I32(Vec<i32>),
+    U8(Vec<u8>),
+}
+
+impl Data {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Data::F64(_) => "Float64",
+            Data::I64(_) => "Int64",
+            Data::I32(_) => "Int32",
+            Data::U8(_) => "UInt8",
+        }
+    }
+
+    fn ascii(&self) -> String {
+        fn join<T: ToString>(v: &[T]) -> String {
+            v.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        match self {
+            Data::F64(v) => join(v),
+            Data::I64(v) => join(v),
+            Data::I32(v) => join(v),
+            Data::U8(v) => join(v),
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        match self {
+            Data::F64(v) => v
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect(),
+            Data::I64(v) => v
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect(),
+            Data::I32(v) => v
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect(),
+            Data::U8(v) => v.clone(),
+        }
+    }
+}
+
+struct Array {
+    name: String,
+    components: usize,
+    data: Data,
+}
+
+impl Array {
+    fn new(
+        name: &str,
+        components: usize,
+        data: Data,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            components,
+            data,
+        }
+    }
+}
+
+/// A writer of a `Tessellation` as VTK polyhedron cells, to be opened
+/// in ParaView or other VTK based tools.
+///
+/// Each cell has the data arrays `id`, `volume` and `face_count`, plus
+/// `neighbor_count` if the tessellation was computed with `VoroCellNbr`.
+/// The neighbor count only includes faces shared with other particles,
+/// not faces on walls or on the container boundary. Further scalar
+/// arrays can be attached with `add_cell_data`.
+///
+/// The vertices are not shared between cells, so that every cell keeps
+/// the exact vertices that voro++ computed for it. To export a container,
+/// write the result of its `tessellate` method.
+pub struct Vtu<'t> {
+    tessellation: &'t Tessellation,
+    cell_data: Vec<(String, Vec<f64>)>,
+}
+
+impl<'t> Vtu<'t> {
+    /// Creates a writer for a tessellation.
+    ///
+    /// * `tessellation`: the cells to write.
+    pub fn new(tessellation: &'t Tessellation) -> Self {
+        Self {
+            tessellation,
+            cell_data: Vec::new(),
+        }
+    }
+
+    /// Attaches a scalar array to the cells.
+    ///
+    /// * `name`: the name of the array.
+    /// * `values`: one value per cell, in the order of
+    ///   `Tessellation::cells`.
+    ///
+    /// Return an error if the number of values does not match the number
+    /// of cells, or if the name is empty or contains characters that are
+    /// not allowed in an XML attribute.
+    pub fn add_cell_data(
+        &mut self,
+        name: &str,
+        values: Vec<f64>,
+    ) -> Result<(), VoroError> {
+        if values.len() != self.tessellation.len() {
+            return Err(VoroError::new(
+                VoroErrorKind::InvalidArgument,
+                "the number of values does not match the number of cells",
+            ));
+        }
+        if name.is_empty()
+            || name.contains(['"', '<', '>', '&'])
+        {
+            return Err(VoroError::new(
+                VoroErrorKind::InvalidArgument,
+                "the name of the array is not a valid XML attribute",
+            ));
+        }
+        self.cell_data.push((name.to_string(), values));
+        Ok(())
+    }
+
+    /// Writes the VTU file.
+    ///
+    /// * `w`: the destination.
+    /// * `format`: the encoding of the data arrays.
+    pub fn write<W: Write>(
+        &self,
+        mut w: W,
+        format: VtuFormat,
+    ) -> io::Result<()> {
+        let cells = self.tessellation.cells();
+        let mut points = Vec::new();
+        let mut connectivity = Vec::new();
+        let mut offsets = Vec::with_capacity(cells.len());
+        let mut faces = Vec::new();
+        let mut face_offsets =
+            Vec::with_capacity(cells.len());
+        for cell in cells {
+            let base = (points.len() / 3) as i64;
+            points.extend(cell.vertices.iter().flatten());
+            connectivity.extend(
+                (0..cell.vertices.len() as i64)
+                    .map(|i| base + i),
+            );
+            offsets.push(connectivity.len() as i64);
+            faces.push(cell.faces.len() as i64);
+            for face in &cell.faces {
+                faces.push(face.vertices.len() as i64);
+                faces.extend(
+                    face.vertices
+                        .iter()
+                        .map(|&v| base + v as i64),
+                );
+            }
+            face_offsets.push(faces.len() as i64);
+        }
+        let n_points = points.len() / 3;
+
+        let point_arrays = vec![Array::new(
+            "Points",
+            3,
+            Data::F64(points),
+        )];
+        let cell_arrays = vec![
+            Array::new(
+                "connectivity",
+                1,
+                Data::I64(connectivity),
+            ),
+            Array::new("offsets", 1, Data::I64(offsets)),
+            // VTK_POLYHEDRON
+            Array::new(
+                "types",
+                1,
+                Data::U8(vec![42; cells.len()]),
+            ),
+            Array::new("faces", 1, Data::I64(faces)),
+            Array::new(
+                "faceoffsets",
+                1,
+                Data::I64(face_offsets),
+            ),
+        ];
+
+        let mut data_arrays = vec![
+            Array::new(
+                "id",
+                1,
+                Data::I32(
+                    cells.iter().map(|c| c.id).collect(),
+                ),
+            ),
+            Array::new(
+                "volume",
+                1,
+                Data::F64(
+                    cells
+                        .iter()
+                        .map(|c| c.volume)
+                        .collect(),
+                ),
+            ),
+            Array::new(
+                "face_count",
+                1,
+                Data::I32(
+                    cells
+                        .iter()
+                        .map(|c| c.faces.len() as i32)
+                        .collect(),
+                ),
+            ),
+        ];
+        let tracked = cells
+            .iter()
+            .flat_map(|c| &c.faces)
+            .any(|f| f.neighbor.is_some());
+        if tracked {
+            data_arrays.push(Array::new(
+                "neighbor_count",
+                1,
+                Data::I32(
+                    cells
+                        .iter()
+                        .map(|c| {
+                            c.neighbors()
+                                .filter(|&n| n >= 0)
+                                .count()
+                                as i32
+                        })
+                        .collect(),
+                ),
+            ));
+        }
+        for (name, values) in &self.cell_data {
+            data_arrays.push(Array::new(
+                name,
+                1,
+                Data::F64(values.clone()),
+            ));
+        }
+
+        let mut appended = Vec::new();
+        let mut section = |w: &mut W,
+                           arrays: &[Array]|
+         -> io::Result<()> {
+            for a in arrays {
+                write!(
+                    w,
+                    "<DataArray type=\"{}\" Name=\"{}\" \
+                     NumberOfComponents=\"{}\" ",
+                    a.data.type_name(),
+                    a.name,
+                    a.components
+                )?;
+                match format {
+                    VtuFormat::Ascii => writeln!(
+                        w,
+                        "format=\"ascii\">\n{}\n</DataArray>",
+                        a.data.ascii()
+                    )?,
+                    VtuFormat::Appended => {
+                        writeln!(
+                            w,
+                            "format=\"appended\" offset=\"{}\"/>",
+                            appended.len()
+                        )?;
+                        let bytes = a.data.bytes();
+                        appended.extend_from_slice(
+                            &(bytes.len() as u64).to_le_bytes(),
+                        );
+                        appended.extend_from_slice(&bytes);
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        writeln!(w, "<?xml version=\"1.0\"?>")?;
+        writeln!(
+            w,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"1.0\" \
+             byte_order=\"LittleEndian\" header_type=\"UInt64\">"
+        )?;
+        writeln!(w, "<UnstructuredGrid>")?;
+        writeln!(
+            w,
+            "<Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">",
+            n_points,
+            cells.len()
+        )?;
+        writeln!(w, "<Points>")?;
+        section(&mut w, &point_arrays)?;
+        writeln!(w, "</Points>")?;
+        writeln!(w, "<Cells>")?;
+        section(&mut w, &cell_arrays)?;
+        writeln!(w, "</Cells>")?;
+        writeln!(w, "<CellData Scalars=\"id\">")?;
+        section(&mut w, &data_arrays)?;
+        writeln!(w, "</CellData>")?;
+        writeln!(w, "</Piece>")?;
+        writeln!(w, "</UnstructuredGrid>")?;
+        if format == VtuFormat::Appended {
+            write!(
+                w,
+                "<AppendedData encoding=\"raw\">\n_"
+            )?;
+            w.write_all(&appended)?;
+            writeln!(w, "\n</AppendedData>")?;
+        }
+        writeln!(w, "</VTKFile>")?;
+        w.flush()
+    }
+
+    /// Writes the VTU file to a path.
+    ///
+    /// * `path`: the path of the file, usually ending in `.vtu`.
+    /// * `format`: the encoding of the data arrays.
+    pub fn write_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: VtuFormat,
+    ) -> io::Result<()> {
+        self.write(
+            BufWriter::new(File::create(path)?),
+            format,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn tessellation<T: VoroCell>() -> Tessellation
+    where
+        for<'c> ContainerStd<'static>:
+            Container2<T, LoopAll<'c>>,
+    {
+        let mut con = ContainerStd::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+            [2, 2, 2],
+            [false, false, false],
+        );
+        con.put(0, [-0.5, 0.0, 0.0], 0.0);
+        con.put(1, [0.5, 0.0, 0.0], 0.0);
+        con.tessellate::<T>()
+    }
+
+    #[test]
+    fn ascii_test() {
+        let tess = tessellation::<VoroCellNbr>();
+        let mut vtu = Vtu::new(&tess);
+        vtu.add_cell_data("pressure", vec![1.0, 2.0])
+            .unwrap();
+        assert!(vtu
+            .add_cell_data("short", vec![1.0])
+            .is_err());
+        assert!(vtu
+            .add_cell_data("a\"b", vec![1.0, 2.0])
+            .is_err());
+
+        let mut out = Vec::new();
+        vtu.write(&mut out, VtuFormat::Ascii).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains(
+            "<Piece NumberOfPoints=\"16\" NumberOfCells=\"2\">"
+        ));
+        assert!(xml.contains("Name=\"neighbor_count\""));
+        assert!(xml.contains("Name=\"face_count\""));
+        assert!(xml.contains("Name=\"pressure\""));
+        assert!(xml.contains("format=\"ascii\">\n42 42\n"));
+        assert!(xml.contains("format=\"ascii\">\n1 1\n"));
+    }
+
+    #[test]
+    fn appended_test() {
+        let tess = tessellation::<VoroCellSgl>();
+        let mut out = Vec::new();
+        Vtu::new(&tess)
+            .write(&mut out, VtuFormat::Appended)
+            .unwrap();
+        let start = out
+            .windows(2)
+            .position(|w| w == b"\n_")
+            .unwrap()
+            + 2;
+        let xml = String::from_utf8_lossy(&out[..start]);
+        assert!(!xml.contains("Name=\"neighbor_count\""));
+        assert!(!xml.contains("format=\"ascii\""));
+
+        // Points, five topology arrays and three cell arrays.
+        let sizes =
+            [48 * 8, 16 * 8, 2 * 8, 2, 62 * 8, 2 * 8];
+        let cells = [2 * 4, 2 * 8, 2 * 4];
+        let len: usize =
+            sizes.iter().chain(&cells).map(|n| n + 8).sum();
+        let end = out.len()
+            - "\n</AppendedData>\n</VTKFile>\n".len();
+        assert_eq!(end - start, len);
+    }
+}
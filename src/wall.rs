@@ -2,6 +2,10 @@
 
 #[cxx::bridge(namespace = "voro")]
 pub mod ffi {
+    extern "Rust" {
+        type WallFn;
+    }
+
     unsafe extern "C++" {
         include!("voro_rs/src/boilerplate.hh");
 
@@ -35,7 +39,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "cut_cell_1"]
         fn cut_cell(
             self: Pin<&mut wall_sphere>,
@@ -43,7 +47,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         type wall_plane;
         #[rust_name = "new_wall_plane"]
@@ -71,7 +75,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "cut_cell_1"]
         fn cut_cell(
             self: Pin<&mut wall_plane>,
@@ -79,7 +83,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         type wall_cylinder;
         #[rust_name = "new_wall_cylinder"]
@@ -110,7 +114,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "cut_cell_1"]
         fn cut_cell(
             self: Pin<&mut wall_cylinder>,
@@ -118,7 +122,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
 
         type wall_cone;
         #[rust_name = "new_wall_cone"]
@@ -149,7 +153,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "cut_cell_1"]
         fn cut_cell(
             self: Pin<&mut wall_cone>,
@@ -157,7 +161,204 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
+        ) -> Result<bool>;
+
+        type wall_ellipsoid;
+        #[rust_name = "new_wall_ellipsoid"]
+        fn construct(
+            xc_: f64,
+            yc_: f64,
+            zc_: f64,
+            xr_: f64,
+            yr_: f64,
+            zr_: f64,
+            w_id_: i32,
+        ) -> UniquePtr<wall_ellipsoid>;
+        #[rust_name = "clone_wall_ellipsoid"]
+        fn clone_wall(
+            value: &UniquePtr<wall_ellipsoid>,
+        ) -> UniquePtr<wall_ellipsoid>;
+        fn point_inside(
+            self: Pin<&mut wall_ellipsoid>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool;
+        #[rust_name = "cut_cell_0"]
+        fn cut_cell(
+            self: Pin<&mut wall_ellipsoid>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "cut_cell_1"]
+        fn cut_cell(
+            self: Pin<&mut wall_ellipsoid>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+
+        type wall_torus;
+        #[rust_name = "new_wall_torus"]
+        fn construct(
+            xc_: f64,
+            yc_: f64,
+            zc_: f64,
+            xa_: f64,
+            ya_: f64,
+            za_: f64,
+            rmaj_: f64,
+            rmin_: f64,
+            w_id_: i32,
+        ) -> UniquePtr<wall_torus>;
+        #[rust_name = "clone_wall_torus"]
+        fn clone_wall(
+            value: &UniquePtr<wall_torus>,
+        ) -> UniquePtr<wall_torus>;
+        fn point_inside(
+            self: Pin<&mut wall_torus>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool;
+        #[rust_name = "cut_cell_0"]
+        fn cut_cell(
+            self: Pin<&mut wall_torus>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "cut_cell_1"]
+        fn cut_cell(
+            self: Pin<&mut wall_torus>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+
+        type wall_box;
+        #[rust_name = "new_wall_box"]
+        fn construct(
+            xmin_: f64,
+            xmax_: f64,
+            ymin_: f64,
+            ymax_: f64,
+            zmin_: f64,
+            zmax_: f64,
+            w_id_: i32,
+        ) -> UniquePtr<wall_box>;
+        #[rust_name = "clone_wall_box"]
+        fn clone_wall(
+            value: &UniquePtr<wall_box>,
+        ) -> UniquePtr<wall_box>;
+        fn point_inside(
+            self: Pin<&mut wall_box>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool;
+        #[rust_name = "cut_cell_0"]
+        fn cut_cell(
+            self: Pin<&mut wall_box>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "cut_cell_1"]
+        fn cut_cell(
+            self: Pin<&mut wall_box>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+
+        type wall_polyhedron;
+        #[rust_name = "new_wall_polyhedron"]
+        fn construct(
+            planes_: &[f64],
+            w_ids_: &[i32],
+        ) -> UniquePtr<wall_polyhedron>;
+        #[rust_name = "clone_wall_polyhedron"]
+        fn clone_wall(
+            value: &UniquePtr<wall_polyhedron>,
+        ) -> UniquePtr<wall_polyhedron>;
+        fn point_inside(
+            self: Pin<&mut wall_polyhedron>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool;
+        #[rust_name = "cut_cell_0"]
+        fn cut_cell(
+            self: Pin<&mut wall_polyhedron>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "cut_cell_1"]
+        fn cut_cell(
+            self: Pin<&mut wall_polyhedron>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+
+        type wall_custom;
+        #[rust_name = "new_wall_custom"]
+        unsafe fn construct(
+            point_inside: unsafe fn(
+                *mut WallFn,
+                f64,
+                f64,
+                f64,
+            ) -> bool,
+            cut_cell_0: unsafe fn(
+                *mut WallFn,
+                *mut voronoicell,
+                f64,
+                f64,
+                f64,
+            ) -> bool,
+            cut_cell_1: unsafe fn(
+                *mut WallFn,
+                *mut voronoicell_neighbor,
+                f64,
+                f64,
+                f64,
+            ) -> bool,
+            context: *mut WallFn,
+        ) -> UniquePtr<wall_custom>;
+        fn point_inside(
+            self: Pin<&mut wall_custom>,
+            x: f64,
+            y: f64,
+            z: f64,
         ) -> bool;
+        #[rust_name = "cut_cell_0"]
+        fn cut_cell(
+            self: Pin<&mut wall_custom>,
+            c: Pin<&mut voronoicell>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
+        #[rust_name = "cut_cell_1"]
+        fn cut_cell(
+            self: Pin<&mut wall_custom>,
+            c: Pin<&mut voronoicell_neighbor>,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> Result<bool>;
 
         type wall;
         fn wall_sphere_to_wall<'a>(
@@ -172,11 +373,33 @@ pub mod ffi {
         fn wall_cone_to_wall<'a>(
             w: Pin<&'a mut wall_cone>,
         ) -> Pin<&'a mut wall>;
+        fn wall_ellipsoid_to_wall<'a>(
+            w: Pin<&'a mut wall_ellipsoid>,
+        ) -> Pin<&'a mut wall>;
+        fn wall_torus_to_wall<'a>(
+            w: Pin<&'a mut wall_torus>,
+        ) -> Pin<&'a mut wall>;
+        fn wall_box_to_wall<'a>(
+            w: Pin<&'a mut wall_box>,
+        ) -> Pin<&'a mut wall>;
+        fn wall_polyhedron_to_wall<'a>(
+            w: Pin<&'a mut wall_polyhedron>,
+        ) -> Pin<&'a mut wall>;
+        fn wall_custom_to_wall<'a>(
+            w: Pin<&'a mut wall_custom>,
+        ) -> Pin<&'a mut wall>;
     }
 }
 
+use crate::error::{
+    check_box, check_positive, check_vector, VoroError,
+    VoroErrorKind,
+};
 use crate::prelude::{VoroCell, VoroCellNbr, VoroCellSgl};
 use cxx::UniquePtr;
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
 
 type DVec3 = [f64; 3];
 
@@ -314,215 +537,1160 @@ impl WallCone {
     }
 }
 
-impl Clone for WallSphere {
-    fn clone(&self) -> Self {
-        Self {
-            inner: ffi::clone_wall_sphere(&self.inner),
-        }
-    }
+/// A class representing an ellipsoidal wall object, whose axes are
+/// aligned with the coordinate axes.
+pub struct WallEllipsoid {
+    pub(crate) inner: UniquePtr<ffi::wall_ellipsoid>,
 }
 
-impl Clone for WallPlane {
-    fn clone(&self) -> Self {
-        Self {
-            inner: ffi::clone_wall_plane(&self.inner),
-        }
+impl WallEllipsoid {
+    /// Constructs an ellipsoid wall object.
+    ///
+    /// * `c`: a position vector for the ellipsoid's center.
+    /// * `r`: the semi-axes of the ellipsoid along the x, y and z axes.
+    ///
+    /// Panics if the arguments are invalid, see `try_new`.
+    pub fn new(c: DVec3, r: DVec3) -> Self {
+        Self::new_with_id(c, r, -99)
     }
-}
 
-impl Clone for WallCylinder {
-    fn clone(&self) -> Self {
-        Self {
-            inner: ffi::clone_wall_cylinder(&self.inner),
-        }
+    /// Fallible version of `new`.
+    pub fn try_new(
+        c: DVec3,
+        r: DVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_id(c, r, -99)
     }
-}
 
-impl Clone for WallCone {
-    fn clone(&self) -> Self {
-        Self {
-            inner: ffi::clone_wall_cone(&self.inner),
-        }
+    /// Constructs an ellipsoid wall object.
+    ///
+    /// * `c`: a position vector for the ellipsoid's center.
+    /// * `r`: the semi-axes of the ellipsoid along the x, y and z axes.
+    /// * `id`: an ID number to associate with the wall for neighbor tracking.
+    ///
+    /// Panics if the arguments are invalid, see `try_new_with_id`.
+    pub fn new_with_id(
+        c: DVec3,
+        r: DVec3,
+        id: i32,
+    ) -> Self {
+        Self::try_new_with_id(c, r, id)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_id`.
+    ///
+    /// Return an error if the center is not finite, or if a semi-axis
+    /// is not finite and positive.
+    pub fn try_new_with_id(
+        c: DVec3,
+        r: DVec3,
+        id: i32,
+    ) -> Result<Self, VoroError> {
+        check_vector("c", c, false)?;
+        check_positive("r", &r)?;
+        Ok(Self {
+            inner: ffi::new_wall_ellipsoid(
+                c[0], c[1], c[2], r[0], r[1], r[2], id,
+            ),
+        })
     }
 }
 
-/// A part of trait `Wall` whose parameter does not depends on cell type.
-pub trait Wall0 {
-    /// Tests to see whether a point is inside the sphere wall object.
+/// A class representing a toroidal wall object.
+pub struct WallTorus {
+    pub(crate) inner: UniquePtr<ffi::wall_torus>,
+}
+
+impl WallTorus {
+    /// Constructs a torus wall object.
     ///
-    /// * `x,y,z`: the vector to test.
+    /// * `c`: a position vector for the torus's center.
+    /// * `a`: a vector pointing along the axis of the torus.
+    /// * `r_major`: the distance from the center to the center of the tube.
+    /// * `r_minor`: the radius of the tube.
     ///
-    /// Return true if the point is inside, false if the point is outside.
-    fn point_inside(&mut self, xyz: DVec3) -> bool;
-}
+    /// Panics if the arguments are invalid, see `try_new`.
+    pub fn new(
+        c: DVec3,
+        a: DVec3,
+        r_major: f64,
+        r_minor: f64,
+    ) -> Self {
+        Self::new_with_id(c, a, r_major, r_minor, -99)
+    }
 
-impl Wall0 for WallSphere {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
+    /// Fallible version of `new`.
+    pub fn try_new(
+        c: DVec3,
+        a: DVec3,
+        r_major: f64,
+        r_minor: f64,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_id(c, a, r_major, r_minor, -99)
     }
-}
 
-impl Wall0 for WallPlane {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
+    /// Constructs a torus wall object.
+    ///
+    /// * `c`: a position vector for the torus's center.
+    /// * `a`: a vector pointing along the axis of the torus.
+    /// * `r_major`: the distance from the center to the center of the tube.
+    /// * `r_minor`: the radius of the tube.
+    /// * `id`: an ID number to associate with the wall for neighbor tracking.
+    ///
+    /// Panics if the arguments are invalid, see `try_new_with_id`.
+    pub fn new_with_id(
+        c: DVec3,
+        a: DVec3,
+        r_major: f64,
+        r_minor: f64,
+        id: i32,
+    ) -> Self {
+        Self::try_new_with_id(c, a, r_major, r_minor, id)
+            .unwrap_or_else(|e| panic!("{e}"))
     }
-}
 
-impl Wall0 for WallCylinder {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
+    /// Fallible version of `new_with_id`.
+    ///
+    /// Return an error if the center is not finite, if the axis is not
+    /// finite or is zero, or if a radius is not finite and positive.
+    pub fn try_new_with_id(
+        c: DVec3,
+        a: DVec3,
+        r_major: f64,
+        r_minor: f64,
+        id: i32,
+    ) -> Result<Self, VoroError> {
+        check_vector("c", c, false)?;
+        check_vector("a", a, true)?;
+        check_positive("r_major", &[r_major])?;
+        check_positive("r_minor", &[r_minor])?;
+        Ok(Self {
+            inner: ffi::new_wall_torus(
+                c[0], c[1], c[2], a[0], a[1], a[2],
+                r_major, r_minor, id,
+            ),
+        })
     }
 }
 
-impl Wall0 for WallCone {
-    fn point_inside(&mut self, xyz: DVec3) -> bool {
-        self.inner
-            .pin_mut()
-            .point_inside(xyz[0], xyz[1], xyz[2])
-    }
+/// A class representing a box wall object, whose faces are aligned with
+/// the coordinate axes.
+pub struct WallBox {
+    pub(crate) inner: UniquePtr<ffi::wall_box>,
 }
 
-/// A part of trait `Wall` whose parameter depends on cell type.
-pub trait Wall1<T: VoroCell> {
-    /// Cuts a cell by the sphere wall object. The spherical wall is approximated by
-    /// a single plane applied at the point on the sphere which is closest to the center
-    /// of the cell. This works well for particle arrangements that are packed against
-    /// the wall, but loses accuracy for sparse particle distributions.
+impl WallBox {
+    /// Constructs a box wall object.
     ///
-    /// * `cell`: the Voronoi cell to be cut.
-    /// * `xyz`: the location of the Voronoi cell.
+    /// * `xyz_min`: the minimum coordinates of the box.
+    /// * `xyz_max`: the maximum coordinates of the box.
     ///
-    /// Return true if the cell still exists, false if the cell is deleted.
-    fn cut_cell(
-        &mut self,
-        cell: &mut T,
-        xyz: DVec3,
-    ) -> bool;
-}
-
-impl Wall1<VoroCellSgl> for WallSphere {
-    fn cut_cell(
-        &mut self,
-        cell: &mut VoroCellSgl,
-        xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().cut_cell_0(
-            cell.inner.pin_mut(),
-            xyz[0],
-            xyz[1],
-            xyz[2],
-        )
+    /// Panics if the arguments are invalid, see `try_new`.
+    pub fn new(xyz_min: DVec3, xyz_max: DVec3) -> Self {
+        Self::new_with_id(xyz_min, xyz_max, -99)
     }
-}
 
-impl Wall1<VoroCellNbr> for WallSphere {
-    fn cut_cell(
-        &mut self,
-        cell: &mut VoroCellNbr,
-        xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().cut_cell_1(
-            cell.inner.pin_mut(),
-            xyz[0],
-            xyz[1],
-            xyz[2],
-        )
+    /// Fallible version of `new`.
+    pub fn try_new(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+    ) -> Result<Self, VoroError> {
+        Self::try_new_with_id(xyz_min, xyz_max, -99)
     }
-}
 
-impl Wall1<VoroCellSgl> for WallPlane {
-    fn cut_cell(
+    /// Constructs a box wall object.
+    ///
+    /// * `xyz_min`: the minimum coordinates of the box.
+    /// * `xyz_max`: the maximum coordinates of the box.
+    /// * `id`: an ID number to associate with the wall for neighbor tracking.
+    ///
+    /// Panics if the arguments are invalid, see `try_new_with_id`.
+    pub fn new_with_id(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        id: i32,
+    ) -> Self {
+        Self::try_new_with_id(xyz_min, xyz_max, id)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `new_with_id`.
+    ///
+    /// Return an error if the bounds are not finite, or if `xyz_min` is
+    /// not less than `xyz_max` in each direction.
+    pub fn try_new_with_id(
+        xyz_min: DVec3,
+        xyz_max: DVec3,
+        id: i32,
+    ) -> Result<Self, VoroError> {
+        check_box(xyz_min, xyz_max)?;
+        Ok(Self {
+            inner: ffi::new_wall_box(
+                xyz_min[0], xyz_max[0], xyz_min[1],
+                xyz_max[1], xyz_min[2], xyz_max[2], id,
+            ),
+        })
+    }
+}
+
+/// A class representing a convex polyhedral wall object.
+///
+/// The polyhedron is the intersection of a list of half-spaces, and a
+/// cell is cut by the plane of every face. Each face has its own wall ID,
+/// counting down from the ID given at construction, so that the faces of
+/// a cell lying on the boundary can be attributed to a face of the wall.
+pub struct WallPolyhedron {
+    pub(crate) inner: UniquePtr<ffi::wall_polyhedron>,
+}
+
+impl WallPolyhedron {
+    /// Constructs a polyhedral wall object from a list of half-spaces.
+    ///
+    /// * `planes`: a list of `(n, a)` pairs, each one for the half-space
+    /// of points `x` with `n . x < a`.
+    ///
+    /// The face `i` is given the ID `-7 - i`, which follows the IDs used
+    /// by the container for its own walls.
+    ///
+    /// Return an error if a normal vector is zero or any value is not
+    /// finite.
+    pub fn from_half_spaces(
+        planes: &[(DVec3, f64)],
+    ) -> Result<Self, VoroError> {
+        Self::from_half_spaces_with_id(planes, -7)
+    }
+
+    /// Constructs a polyhedral wall object from a list of half-spaces.
+    ///
+    /// * `planes`: a list of `(n, a)` pairs, each one for the half-space
+    /// of points `x` with `n . x < a`.
+    /// * `id`: the ID of the first face. The face `i` is given the ID
+    /// `id - i`.
+    ///
+    /// Return an error if a normal vector is zero or any value is not
+    /// finite.
+    pub fn from_half_spaces_with_id(
+        planes: &[(DVec3, f64)],
+        id: i32,
+    ) -> Result<Self, VoroError> {
+        let mut values =
+            Vec::with_capacity(4 * planes.len());
+        for &(n, a) in planes {
+            let norm = dot(n, n).sqrt();
+            if !(norm.is_finite() && a.is_finite())
+                || norm == 0.0
+            {
+                return Err(VoroError::new(
+                    VoroErrorKind::InvalidArgument,
+                    format!(
+                        "invalid half-space with normal {n:?} and offset {a}"
+                    ),
+                ));
+            }
+            values.extend([
+                n[0] / norm,
+                n[1] / norm,
+                n[2] / norm,
+                a / norm,
+            ]);
+        }
+        let ids: Vec<i32> = (0..planes.len() as i32)
+            .map(|i| id - i)
+            .collect();
+        Ok(Self {
+            inner: ffi::new_wall_polyhedron(&values, &ids),
+        })
+    }
+
+    /// Constructs a polyhedral wall object from a closed convex triangle
+    /// mesh.
+    ///
+    /// * `vertices`: the position of the vertices.
+    /// * `triangles`: the indices of the vertices of each triangle. The
+    /// triangles may be wound either way, but coplanar triangles are
+    /// merged into a single face.
+    ///
+    /// The faces are given IDs as in `from_half_spaces`.
+    ///
+    /// Return an error if the mesh is not closed, not convex, or has a
+    /// degenerate triangle.
+    pub fn from_mesh(
+        vertices: &[DVec3],
+        triangles: &[[usize; 3]],
+    ) -> Result<Self, VoroError> {
+        Self::from_mesh_with_id(vertices, triangles, -7)
+    }
+
+    /// Constructs a polyhedral wall object from a closed convex triangle
+    /// mesh.
+    ///
+    /// * `vertices`: the position of the vertices.
+    /// * `triangles`: the indices of the vertices of each triangle. The
+    /// triangles may be wound either way, but coplanar triangles are
+    /// merged into a single face.
+    /// * `id`: the ID of the first face. The face `i` is given the ID
+    /// `id - i`.
+    ///
+    /// Return an error if the mesh is not closed, not convex, or has a
+    /// degenerate triangle.
+    pub fn from_mesh_with_id(
+        vertices: &[DVec3],
+        triangles: &[[usize; 3]],
+        id: i32,
+    ) -> Result<Self, VoroError> {
+        let planes = mesh_planes(vertices, triangles)?;
+        Self::from_half_spaces_with_id(&planes, id)
+    }
+}
+
+fn invalid_mesh(message: impl Into<String>) -> VoroError {
+    VoroError::new(VoroErrorKind::InvalidArgument, message)
+}
+
+fn dot(a: DVec3, b: DVec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn sub(a: DVec3, b: DVec3) -> DVec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: DVec3, b: DVec3) -> DVec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Computes the outward face planes of a closed convex triangle mesh,
+/// with unit normals.
+fn mesh_planes(
+    vertices: &[DVec3],
+    triangles: &[[usize; 3]],
+) -> Result<Vec<(DVec3, f64)>, VoroError> {
+    if triangles.len() < 4 {
+        return Err(invalid_mesh(
+            "a closed mesh needs at least 4 triangles",
+        ));
+    }
+    // every edge must be shared by exactly two triangles.
+    let mut edges = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        if tri.iter().any(|&i| i >= vertices.len()) {
+            return Err(invalid_mesh(format!(
+                "triangle {t} refers to a missing vertex"
+            )));
+        }
+        for k in 0..3 {
+            let (i, j) = (tri[k], tri[(k + 1) % 3]);
+            *edges
+                .entry((i.min(j), i.max(j)))
+                .or_insert(0) += 1;
+        }
+    }
+    if edges.values().any(|&count| count != 2) {
+        return Err(invalid_mesh("the mesh is not closed"));
+    }
+
+    // only the vertices of the triangles are part of the mesh.
+    let mut used: Vec<usize> =
+        triangles.iter().flatten().copied().collect();
+    used.sort_unstable();
+    used.dedup();
+    let used: Vec<DVec3> =
+        used.into_iter().map(|i| vertices[i]).collect();
+    if used.iter().flatten().any(|v| !v.is_finite()) {
+        return Err(invalid_mesh(
+            "vertices must be finite",
+        ));
+    }
+
+    let scale = used
+        .iter()
+        .flatten()
+        .fold(0.0f64, |m, v| m.max(v.abs()));
+    let eps = 1e-10 * scale.max(1.0);
+    // the normal of a small triangle is less accurate than its vertices,
+    // so coplanar triangles are matched with a looser tolerance.
+    let normal_eps = 1e-8;
+
+    let mut planes: Vec<(DVec3, f64)> = Vec::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        let [a, b, c] = tri.map(|i| vertices[i]);
+        let n = cross(sub(b, a), sub(c, a));
+        let norm = dot(n, n).sqrt();
+        if norm <= eps * scale.max(1.0) {
+            return Err(invalid_mesh(format!(
+                "triangle {t} is degenerate"
+            )));
+        }
+        let mut n = n.map(|x| x / norm);
+        let mut offset = dot(n, a);
+
+        // orient the normal so that the mesh lies behind the plane.
+        let (lo, hi) = used.iter().fold(
+            (0.0f64, 0.0f64),
+            |(lo, hi), &v| {
+                let d = dot(n, v) - offset;
+                (lo.min(d), hi.max(d))
+            },
+        );
+        if hi > eps && lo < -eps {
+            return Err(invalid_mesh(format!(
+                "the mesh is not convex at triangle {t}"
+            )));
+        }
+        if hi > eps {
+            n = n.map(|x| -x);
+            offset = -offset;
+        }
+
+        let duplicate = planes.iter().any(|&(m, d)| {
+            (0..3)
+                .all(|i| (m[i] - n[i]).abs() <= normal_eps)
+                && (d - offset).abs() <= eps
+        });
+        if !duplicate {
+            planes.push((n, offset));
+        }
+    }
+    Ok(planes)
+}
+
+pub struct WallFn {
+    wall: Box<dyn Wall + Send>,
+}
+
+/// `wall` subclass whose geometry is defined in Rust.
+///
+/// A class wrapping any type implementing the `Wall` trait, so it can be
+/// added to a `WallList` or a container like the built-in walls. voro++
+/// calls back into the wrapped wall whenever it tests a point or cuts a
+/// cell. A panic inside these callbacks aborts the process.
+///
+/// This class does not implement `Clone` trait because the wrapped wall
+/// is not required to be clonable. It is `Send` but not `Sync`, since the
+/// wrapped wall is called through `&mut self`.
+pub struct WallCustom {
+    pub(crate) inner: UniquePtr<ffi::wall_custom>,
+    // owned through a raw pointer, since `inner` keeps a copy of it.
+    context: *mut WallFn,
+}
+
+impl WallCustom {
+    /// Constructs a wall object from a Rust wall.
+    ///
+    /// * `wall`: the wall that defines the geometry.
+    pub fn new<T: Wall + Send + 'static>(wall: T) -> Self {
+        fn point_inside(
+            context: *mut WallFn,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool {
+            unsafe {
+                (*context).wall.point_inside([x, y, z])
+            }
+        }
+
+        fn cut_cell_0(
+            context: *mut WallFn,
+            c: *mut ffi::voronoicell,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool {
+            unsafe {
+                // the cell is owned by voro++, so it must not be
+                // dropped.
+                let mut cell =
+                    ManuallyDrop::new(VoroCellSgl {
+                        inner: UniquePtr::from_raw(c),
+                    });
+                (*context)
+                    .wall
+                    .cut_cell(&mut *cell, [x, y, z])
+            }
+        }
+
+        fn cut_cell_1(
+            context: *mut WallFn,
+            c: *mut ffi::voronoicell_neighbor,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> bool {
+            unsafe {
+                // the cell is owned by voro++, so it must not be
+                // dropped.
+                let mut cell =
+                    ManuallyDrop::new(VoroCellNbr {
+                        inner: UniquePtr::from_raw(c),
+                    });
+                (*context)
+                    .wall
+                    .cut_cell(&mut *cell, [x, y, z])
+            }
+        }
+
+        let context = Box::into_raw(Box::new(WallFn {
+            wall: Box::new(wall),
+        }));
+        Self {
+            inner: unsafe {
+                ffi::new_wall_custom(
+                    point_inside,
+                    cut_cell_0,
+                    cut_cell_1,
+                    context,
+                )
+            },
+            context,
+        }
+    }
+}
+
+// SAFETY: the context is owned by this struct, and the wrapped wall is
+// `Send`.
+unsafe impl Send for WallCustom {}
+
+impl Drop for WallCustom {
+    fn drop(&mut self) {
+        unsafe {
+            // `inner` never calls back while it is being destroyed.
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+impl Clone for WallSphere {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_sphere(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallPlane {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_plane(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallCylinder {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_cylinder(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallCone {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_cone(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallEllipsoid {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_ellipsoid(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallTorus {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_torus(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallBox {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_box(&self.inner),
+        }
+    }
+}
+
+impl Clone for WallPolyhedron {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ffi::clone_wall_polyhedron(&self.inner),
+        }
+    }
+}
+
+// SAFETY: the built-in walls own all of their data and have no mutable
+// state. The only method taking `&self` is `clone`, which only reads the
+// wall.
+unsafe impl Send for WallSphere {}
+unsafe impl Sync for WallSphere {}
+unsafe impl Send for WallPlane {}
+unsafe impl Sync for WallPlane {}
+unsafe impl Send for WallCylinder {}
+unsafe impl Sync for WallCylinder {}
+unsafe impl Send for WallCone {}
+unsafe impl Sync for WallCone {}
+unsafe impl Send for WallEllipsoid {}
+unsafe impl Sync for WallEllipsoid {}
+unsafe impl Send for WallTorus {}
+unsafe impl Sync for WallTorus {}
+unsafe impl Send for WallBox {}
+unsafe impl Sync for WallBox {}
+unsafe impl Send for WallPolyhedron {}
+unsafe impl Sync for WallPolyhedron {}
+
+/// A part of trait `Wall` whose parameter does not depends on cell type.
+pub trait Wall0 {
+    /// Tests to see whether a point is inside the sphere wall object.
+    ///
+    /// * `x,y,z`: the vector to test.
+    ///
+    /// Return true if the point is inside, false if the point is outside.
+    fn point_inside(&mut self, xyz: DVec3) -> bool;
+}
+
+impl Wall0 for WallSphere {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallPlane {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallCylinder {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallCone {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallEllipsoid {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallTorus {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallBox {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallPolyhedron {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+impl Wall0 for WallCustom {
+    fn point_inside(&mut self, xyz: DVec3) -> bool {
+        self.inner
+            .pin_mut()
+            .point_inside(xyz[0], xyz[1], xyz[2])
+    }
+}
+
+/// A part of trait `Wall` whose parameter depends on cell type.
+pub trait Wall1<T: VoroCell> {
+    /// Cuts a cell by the sphere wall object. The spherical wall is approximated by
+    /// a single plane applied at the point on the sphere which is closest to the center
+    /// of the cell. This works well for particle arrangements that are packed against
+    /// the wall, but loses accuracy for sparse particle distributions.
+    ///
+    /// * `cell`: the Voronoi cell to be cut.
+    /// * `xyz`: the location of the Voronoi cell.
+    ///
+    /// Return true if the cell still exists, false if the cell is deleted.
+    fn cut_cell(
+        &mut self,
+        cell: &mut T,
+        xyz: DVec3,
+    ) -> bool;
+
+    /// Fallible version of `cut_cell`.
+    ///
+    /// Return an error if voro++ reports a fatal error while cutting the
+    /// cell. The built-in walls implement this, while the default
+    /// forwards to `cut_cell`, so that a Rust wall only needs `cut_cell`.
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut T,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.cut_cell(cell, xyz))
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallSphere {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallSphere {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallPlane {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallPlane {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallCylinder {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallCylinder {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallCone {
+    fn cut_cell(
         &mut self,
         cell: &mut VoroCellSgl,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_0(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
-impl Wall1<VoroCellNbr> for WallPlane {
+impl Wall1<VoroCellNbr> for WallCone {
     fn cut_cell(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_1(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
-impl Wall1<VoroCellSgl> for WallCylinder {
+impl Wall1<VoroCellSgl> for WallEllipsoid {
     fn cut_cell(
         &mut self,
         cell: &mut VoroCellSgl,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_0(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
-impl Wall1<VoroCellNbr> for WallCylinder {
+impl Wall1<VoroCellNbr> for WallEllipsoid {
     fn cut_cell(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_1(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
-impl Wall1<VoroCellSgl> for WallCone {
+impl Wall1<VoroCellSgl> for WallTorus {
     fn cut_cell(
         &mut self,
         cell: &mut VoroCellSgl,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_0(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
-impl Wall1<VoroCellNbr> for WallCone {
+impl Wall1<VoroCellNbr> for WallTorus {
     fn cut_cell(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
     ) -> bool {
-        self.inner.pin_mut().cut_cell_1(
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallBox {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallBox {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallPolyhedron {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallPolyhedron {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellSgl> for WallCustom {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellSgl,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_0(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
+    }
+}
+
+impl Wall1<VoroCellNbr> for WallCustom {
+    fn cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> bool {
+        self.try_cut_cell(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_cut_cell(
+        &mut self,
+        cell: &mut VoroCellNbr,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().cut_cell_1(
+            cell.inner.pin_mut(),
+            xyz[0],
+            xyz[1],
+            xyz[2],
+        )?)
     }
 }
 
@@ -539,6 +1707,82 @@ impl Wall for WallSphere {}
 impl Wall for WallPlane {}
 impl Wall for WallCylinder {}
 impl Wall for WallCone {}
+impl Wall for WallEllipsoid {}
+impl Wall for WallTorus {}
+impl Wall for WallBox {}
+impl Wall for WallPolyhedron {}
+impl Wall for WallCustom {}
+
+pub(crate) mod sealed {
+    use super::ffi;
+    use std::pin::Pin;
+
+    /// The walls backed by a C++ wall object, which can be added to the
+    /// containers and wall lists by pointer.
+    pub trait WallBase {
+        /// Whether the wall calls back into Rust code, see `WallCustom`.
+        const CUSTOM: bool = false;
+
+        /// Return the C++ wall object.
+        fn base(&mut self) -> Pin<&mut ffi::wall>;
+    }
+}
+
+impl sealed::WallBase for WallSphere {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_sphere_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallPlane {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_plane_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallCylinder {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_cylinder_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallCone {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_cone_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallEllipsoid {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_ellipsoid_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallTorus {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_torus_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallBox {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_box_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallPolyhedron {
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_polyhedron_to_wall(self.inner.pin_mut())
+    }
+}
+
+impl sealed::WallBase for WallCustom {
+    const CUSTOM: bool = true;
+
+    fn base(&mut self) -> Pin<&mut ffi::wall> {
+        ffi::wall_custom_to_wall(self.inner.pin_mut())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -615,4 +1859,247 @@ mod tests {
             1.0,
         );
     }
+
+    #[test]
+    fn shapes_test() {
+        let unit_cell = || {
+            VoroCellSgl::new(
+                [-1.0, -1.0, -1.0],
+                [1.0, 1.0, 1.0],
+            )
+        };
+
+        let mut w0 = WallEllipsoid::new(
+            [0.0, 0.0, 0.0],
+            [10.0, 1.0, 1.0],
+        );
+        assert!(w0.point_inside([9.0, 0.0, 0.0]));
+        assert!(!w0.point_inside([0.0, 1.5, 0.0]));
+        let mut c0 = unit_cell();
+        assert!(w0.cut_cell(&mut c0, [0.0, 0.5, 0.0]));
+        assert!((c0.volume() - 6.0).abs() < 1e-10);
+
+        let mut w1 = WallTorus::new(
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            5.0,
+            1.0,
+        );
+        assert!(w1.point_inside([0.0, 5.0, 0.5]));
+        assert!(!w1.point_inside([0.0, 0.0, 0.0]));
+        let mut c1 = unit_cell();
+        assert!(w1.cut_cell(&mut c1, [5.0, 0.0, 0.5]));
+        assert!((c1.volume() - 6.0).abs() < 1e-10);
+
+        let mut w2 = WallBox::new(
+            [-0.5, -0.5, -0.5],
+            [0.5, 0.5, 0.5],
+        );
+        assert!(w2.point_inside([0.0, 0.0, 0.0]));
+        assert!(!w2.point_inside([0.0, 0.0, 0.7]));
+        let mut c2 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w2
+            .clone()
+            .cut_cell(&mut c2, [0.0, 0.0, 0.0]));
+        assert_eq!(c2.volume(), 1.0);
+    }
+
+    #[test]
+    fn invalid_argument_test() {
+        let c = [0.0, 0.0, 0.0];
+        assert!(WallEllipsoid::try_new(c, [1.0, 0.0, 1.0])
+            .is_err());
+        assert!(WallEllipsoid::try_new(
+            [f64::NAN, 0.0, 0.0],
+            [1.0, 1.0, 1.0]
+        )
+        .is_err());
+        assert!(WallTorus::try_new(c, [0.0; 3], 5.0, 1.0)
+            .is_err());
+        assert!(WallTorus::try_new(
+            c,
+            [0.0, 0.0, 1.0],
+            5.0,
+            -1.0
+        )
+        .is_err());
+        assert!(WallTorus::try_new(
+            c,
+            [0.0, 0.0, 1.0],
+            f64::INFINITY,
+            1.0
+        )
+        .is_err());
+        let e = WallBox::try_new([0.0, 0.0, 1.0], [1.0; 3])
+            .err()
+            .unwrap();
+        assert_eq!(
+            e.kind(),
+            VoroErrorKind::InvalidArgument
+        );
+        assert!(WallBox::try_new(c, [1.0; 3]).is_ok());
+    }
+
+    #[test]
+    fn polyhedron_test() {
+        let vertices = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ];
+        let triangles = [
+            [0, 1, 2],
+            [0, 2, 3],
+            [4, 5, 6],
+            [4, 6, 7],
+            [0, 1, 5],
+            [0, 5, 4],
+            [1, 2, 6],
+            [1, 6, 5],
+            [2, 3, 7],
+            [2, 7, 6],
+            [3, 0, 4],
+            [3, 4, 7],
+        ];
+        let mut w0 = WallPolyhedron::from_mesh(
+            &vertices, &triangles,
+        )
+        .unwrap();
+        assert!(w0.point_inside([0.5, 0.5, 0.5]));
+        assert!(!w0.point_inside([1.5, 0.5, 0.5]));
+
+        let mut c0 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w0.cut_cell(&mut c0, [0.5, 0.5, 0.5]));
+        assert!((c0.volume() - 1.0).abs() < 1e-10);
+        let mut ids = c0.neighbors();
+        ids.sort();
+        assert_eq!(ids, vec![-12, -11, -10, -9, -8, -7]);
+
+        // a dent in the top face.
+        let mut dented = vertices;
+        dented[6] = [0.5, 0.5, 0.5];
+        let e =
+            WallPolyhedron::from_mesh(&dented, &triangles)
+                .err()
+                .unwrap();
+        assert_eq!(
+            e.kind(),
+            VoroErrorKind::InvalidArgument
+        );
+        assert!(WallPolyhedron::from_mesh(
+            &vertices,
+            &triangles[..11]
+        )
+        .is_err());
+
+        // vertices that no triangle refers to are ignored.
+        let mut extra = vertices.to_vec();
+        extra.push([5.0, 5.0, 5.0]);
+        let mut w2 =
+            WallPolyhedron::from_mesh(&extra, &triangles)
+                .unwrap();
+        let mut c2 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w2.cut_cell(&mut c2, [0.5, 0.5, 0.5]));
+        assert_eq!(c2.neighbors().len(), 6);
+
+        let mut w1 =
+            WallPolyhedron::from_half_spaces_with_id(
+                &[
+                    ([0.0, 0.0, 2.0], 1.0),
+                    ([0.0, 0.0, -1.0], 0.5),
+                ],
+                -20,
+            )
+            .unwrap();
+        let mut c1 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w1.cut_cell(&mut c1, [0.0, 0.0, 0.0]));
+        assert!((c1.volume() - 4.0).abs() < 1e-10);
+        assert!(WallPolyhedron::from_half_spaces(&[(
+            [0.0, 0.0, 0.0],
+            1.0
+        )])
+        .is_err());
+    }
+
+    struct HalfSpace {
+        a: f64,
+    }
+
+    impl Wall0 for HalfSpace {
+        fn point_inside(&mut self, xyz: DVec3) -> bool {
+            xyz[0] < self.a
+        }
+    }
+
+    impl<T: VoroCell> Wall1<T> for HalfSpace {
+        fn cut_cell(
+            &mut self,
+            cell: &mut T,
+            xyz: DVec3,
+        ) -> bool {
+            let rsq = 2.0 * (self.a - xyz[0]);
+            cell.nplane_rsq([1.0, 0.0, 0.0], rsq, -7)
+        }
+    }
+
+    impl Wall for HalfSpace {}
+
+    #[test]
+    fn custom_test() {
+        let mut w0 = WallCustom::new(HalfSpace { a: 0.5 });
+        assert!(w0.point_inside([0.0, 0.0, 0.0]));
+        assert!(!w0.point_inside([1.0, 0.0, 0.0]));
+
+        let mut c0 = VoroCellSgl::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w0.cut_cell(&mut c0, [0.0, 0.0, 0.0]));
+        assert_eq!(c0.volume(), 6.0);
+
+        let mut c1 = VoroCellNbr::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        assert!(w0
+            .try_cut_cell(&mut c1, [0.0, 0.0, 0.0])
+            .unwrap());
+        assert_eq!(c1.volume(), 6.0);
+    }
+
+    #[test]
+    fn send_test() {
+        let w0 = WallSphere::new([10.0, 0.0, 0.0], 10.0);
+        let w1 = WallCustom::new(HalfSpace { a: 0.5 });
+        let mut c0 = std::thread::spawn(move || {
+            let (mut w0, mut w1) = (w0, w1);
+            let mut c0 = VoroCellSgl::new(
+                [-1.0, -1.0, -1.0],
+                [1.0, 1.0, 1.0],
+            );
+            w0.cut_cell(&mut c0, [0.0, 0.0, 0.0]);
+            w1.cut_cell(&mut c0, [0.0, 0.0, 0.0]);
+            c0
+        })
+        .join()
+        .unwrap();
+        assert_eq!(c0.volume(), 4.0);
+    }
 }
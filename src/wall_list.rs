@@ -17,12 +17,12 @@ pub mod ffi {
         unsafe fn add_wall(
             self: Pin<&mut wall_list>,
             w: Pin<&mut wall>,
-        );
+        ) -> Result<()>;
         #[rust_name = "add_walls"]
         unsafe fn add_wall(
             self: Pin<&mut wall_list>,
             w: Pin<&mut wall_list>,
-        );
+        ) -> Result<()>;
         fn point_inside_walls(
             self: Pin<&mut wall_list>,
             x: f64,
@@ -36,7 +36,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
         #[rust_name = "apply_walls_1"]
         fn apply_walls(
             self: Pin<&mut wall_list>,
@@ -44,7 +44,7 @@ pub mod ffi {
             x: f64,
             y: f64,
             z: f64,
-        ) -> bool;
+        ) -> Result<bool>;
     }
 }
 
@@ -53,12 +53,19 @@ use crate::container::ffi::{
     container_poly_to_wall_list, container_to_wall_list,
 };
 use crate::container::{ContainerRad, ContainerStd};
+use crate::error::VoroError;
 use crate::wall::ffi::{
-    wall_cone_to_wall, wall_cylinder_to_wall,
-    wall_plane_to_wall, wall_sphere_to_wall,
+    wall_box_to_wall, wall_cone_to_wall,
+    wall_custom_to_wall, wall_cylinder_to_wall,
+    wall_ellipsoid_to_wall, wall_plane_to_wall,
+    wall_polyhedron_to_wall, wall_sphere_to_wall,
+    wall_torus_to_wall,
 };
+use crate::wall::sealed::WallBase;
 use crate::wall::{
-    Wall, WallCone, WallCylinder, WallPlane, WallSphere,
+    Wall, WallBox, WallCone, WallCustom, WallCylinder,
+    WallEllipsoid, WallPlane, WallPolyhedron, WallSphere,
+    WallTorus,
 };
 use cxx::UniquePtr;
 use std::marker::PhantomData;
@@ -77,17 +84,85 @@ type DVec3 = [f64; 3];
 /// this struct.
 pub struct WallList<'a> {
     pub(crate) inner: UniquePtr<ffi::wall_list>,
+    // declared after `inner`, so that the walls are dropped after the
+    // list referring to them.
+    owned_walls: Vec<Box<dyn Wall + Send>>,
+    // whether a `WallCustom` was added, whose callbacks may not be
+    // called from several threads at once.
+    pub(crate) has_custom_walls: bool,
     phantom: PhantomData<&'a ()>,
 }
 
+// SAFETY: the list only refers to walls that are `Send`, either borrowed
+// for `'a` or owned by this struct, and no method reads it through
+// `&self`.
+unsafe impl Send for WallList<'_> {}
+
 impl<'a> WallList<'a> {
     /// Create an empty `WallList`
     pub fn new() -> Self {
         Self {
             inner: ffi::new_wall_list(),
+            owned_walls: Vec::new(),
+            has_custom_walls: false,
             phantom: PhantomData,
         }
     }
+
+    /// Adds a wall to the list, taking ownership of it.
+    ///
+    /// * `wall`: the wall to add. Unlike `add_wall`, the wall is kept
+    /// alive by this struct, so it does not need to outlive it.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_add_wall_owned`.
+    pub fn add_wall_owned<T>(&mut self, wall: T)
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.try_add_wall_owned(wall)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `add_wall_owned`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// more walls are added than voro++ can store. The wall is dropped in
+    /// that case.
+    pub fn try_add_wall_owned<T>(
+        &mut self,
+        wall: T,
+    ) -> Result<(), VoroError>
+    where
+        T: Wall + WallBase + Send + 'static,
+    {
+        self.owned_walls.push(Box::new(wall));
+        let wall = self.owned_walls.last_mut().unwrap();
+        let wall = wall.as_mut() as *mut (dyn Wall + Send)
+            as *mut T;
+        // SAFETY: the wall is heap-allocated and owned by `owned_walls`,
+        // which is dropped after `inner`, and no reference to it is kept.
+        let result = unsafe { self.try_add_wall_ptr(wall) };
+        if result.is_err() {
+            self.owned_walls.pop();
+        }
+        result
+    }
+
+    /// Adds the C++ object of a wall to the list.
+    ///
+    /// # Safety
+    ///
+    /// `wall` must be valid and not aliased for this call, and must stay
+    /// in place until `inner` is dropped.
+    unsafe fn try_add_wall_ptr<T: WallBase>(
+        &mut self,
+        wall: *mut T,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= T::CUSTOM;
+        let w0 = unsafe { (*wall).base() };
+        unsafe { self.inner.pin_mut().add_wall(w0)? };
+        Ok(())
+    }
 }
 
 /// A part of trait `Wall` whose parameter does not depends any type.
@@ -118,40 +193,55 @@ pub trait Walls1<T: VoroCell> {
     ///
     /// Return true if the cell still exists, false if the cell is
     /// deleted.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_apply_walls`.
     fn apply_walls(
         &mut self,
         cell: &mut T,
         xyz: DVec3,
-    ) -> bool;
+    ) -> bool {
+        self.try_apply_walls(cell, xyz)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `apply_walls`.
+    ///
+    /// Return an error if voro++ reports a fatal error while cutting the
+    /// cell.
+    fn try_apply_walls(
+        &mut self,
+        cell: &mut T,
+        xyz: DVec3,
+    ) -> Result<bool, VoroError>;
 }
 
 impl<'a> Walls1<VoroCellSgl> for WallList<'a> {
-    fn apply_walls(
+    fn try_apply_walls(
         &mut self,
         cell: &mut VoroCellSgl,
         xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_0(
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_0(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
 impl<'a> Walls1<VoroCellNbr> for WallList<'a> {
-    fn apply_walls(
+    fn try_apply_walls(
         &mut self,
         cell: &mut VoroCellNbr,
         xyz: DVec3,
-    ) -> bool {
-        self.inner.pin_mut().apply_walls_1(
+    ) -> Result<bool, VoroError> {
+        Ok(self.inner.pin_mut().apply_walls_1(
             cell.inner.pin_mut(),
             xyz[0],
             xyz[1],
             xyz[2],
-        )
+        )?)
     }
 }
 
@@ -162,51 +252,159 @@ pub trait Walls2<'a, T: Wall> {
     /// * `wall`: a reference to the wall to add. Since this method will not
     /// take ownership nor copy the wall, the `wall`
     /// need to outlive the struct holding it.
-    fn add_wall(&mut self, wall: &'a mut T);
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_add_wall`.
+    fn add_wall(&mut self, wall: &'a mut T) {
+        self.try_add_wall(wall)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `add_wall`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// more walls are added than voro++ can store.
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut T,
+    ) -> Result<(), VoroError>;
 }
 
 impl<'a> Walls2<'a, WallSphere> for WallList<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallSphere) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallSphere,
+    ) -> Result<(), VoroError> {
         let w0 = wall_sphere_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallPlane> for WallList<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallPlane) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPlane,
+    ) -> Result<(), VoroError> {
         let w0 = wall_plane_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCylinder> for WallList<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCylinder) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCylinder,
+    ) -> Result<(), VoroError> {
         let w0 =
             wall_cylinder_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls2<'a, WallCone> for WallList<'a> {
-    fn add_wall(&mut self, wall: &'a mut WallCone) {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCone,
+    ) -> Result<(), VoroError> {
         let w0 = wall_cone_to_wall(wall.inner.pin_mut());
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.
-            self.inner.pin_mut().add_wall(w0);
+            self.inner.pin_mut().add_wall(w0)?;
         }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallEllipsoid> for WallList<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallEllipsoid,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_ellipsoid_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallTorus> for WallList<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallTorus,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_torus_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallBox> for WallList<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallBox,
+    ) -> Result<(), VoroError> {
+        let w0 = wall_box_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallPolyhedron> for WallList<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallPolyhedron,
+    ) -> Result<(), VoroError> {
+        let w0 =
+            wall_polyhedron_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Walls2<'a, WallCustom> for WallList<'a> {
+    fn try_add_wall(
+        &mut self,
+        wall: &'a mut WallCustom,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls = true;
+        let w0 = wall_custom_to_wall(wall.inner.pin_mut());
+        unsafe {
+            // ensure the lifetime of `self` is within the lifetime of
+            // `wall` using the lifetime specifier `'a`.
+            self.inner.pin_mut().add_wall(w0)?;
+        }
+        Ok(())
     }
 }
 
@@ -216,24 +414,49 @@ pub trait Walls3<'a, T: Walls<'a>> {
     ///
     /// * `walls`: a reference to the `Walls` struct. Since this method
     /// will not take ownership nor copy the wall, the `walls` need to
-    /// outlive the struct holding it.
-    fn add_walls(&mut self, walls: &mut T);
+    /// outlive the struct holding it. It is borrowed for `'a`, since it
+    /// may own some of the walls that are added.
+    ///
+    /// Panics if voro++ reports a fatal error, see `try_add_walls`.
+    fn add_walls(&mut self, walls: &'a mut T) {
+        self.try_add_walls(walls)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fallible version of `add_walls`.
+    ///
+    /// Return an error if voro++ reports a fatal error, for instance when
+    /// more walls are added than voro++ can store. The walls before the
+    /// failing one stay in this struct.
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut T,
+    ) -> Result<(), VoroError>;
 }
 
 impl<'a> Walls3<'a, WallList<'a>> for WallList<'a> {
-    fn add_walls(&mut self, walls: &mut WallList<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut WallList<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
             self.inner
                 .pin_mut()
-                .add_walls(walls.inner.pin_mut())
+                .add_walls(walls.inner.pin_mut())?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerStd<'a>> for WallList<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerStd<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerStd<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -241,13 +464,18 @@ impl<'a> Walls3<'a, ContainerStd<'a>> for WallList<'a> {
                 container_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
 impl<'a> Walls3<'a, ContainerRad<'a>> for WallList<'a> {
-    fn add_walls(&mut self, walls: &mut ContainerRad<'a>) {
+    fn try_add_walls(
+        &mut self,
+        walls: &'a mut ContainerRad<'a>,
+    ) -> Result<(), VoroError> {
+        self.has_custom_walls |= walls.has_custom_walls;
         unsafe {
             // ensure the lifetime of `self` is within the lifetime of
             // `wall` using the lifetime specifier `'a`.self
@@ -255,8 +483,9 @@ impl<'a> Walls3<'a, ContainerRad<'a>> for WallList<'a> {
                 container_poly_to_wall_list(
                     walls.inner.pin_mut(),
                 ),
-            )
+            )?;
         }
+        Ok(())
     }
 }
 
@@ -273,6 +502,11 @@ pub trait Walls<'a>:
     + Walls2<'a, WallPlane>
     + Walls2<'a, WallCylinder>
     + Walls2<'a, WallCone>
+    + Walls2<'a, WallEllipsoid>
+    + Walls2<'a, WallTorus>
+    + Walls2<'a, WallBox>
+    + Walls2<'a, WallPolyhedron>
+    + Walls2<'a, WallCustom>
     + Walls3<'a, WallList<'a>>
     + Walls3<'a, ContainerStd<'a>>
     + Walls3<'a, ContainerRad<'a>>
@@ -327,4 +561,43 @@ mod tests {
         wl2.apply_walls(&mut c3, [0.0, 0.0, 0.0]);
         assert_eq!(c3.volume(), 2.0);
     }
+
+    #[test]
+    fn owned_test() {
+        let mut c0 = VoroCellSgl::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let mut wl = WallList::new();
+        wl.add_wall_owned(WallSphere::new(
+            [0.0, 0.0, 100.0],
+            100.0,
+        ));
+        wl.add_wall_owned(WallSphere::new(
+            [0.0, 100.0, 0.0],
+            100.0,
+        ));
+        wl.apply_walls(&mut c0, [0.0, 0.0, 0.0]);
+        assert_eq!(c0.volume(), 2.0);
+    }
+
+    #[test]
+    fn custom_test() {
+        let mut c0 = VoroCellSgl::new(
+            [-1.0, -1.0, -1.0],
+            [1.0, 1.0, 1.0],
+        );
+        let mut w0 = WallCustom::new(WallSphere::new(
+            [0.0, 0.0, 100.0],
+            100.0,
+        ));
+        let mut wl = WallList::new();
+        wl.add_wall(&mut w0);
+        wl.add_wall_owned(WallCustom::new(
+            WallSphere::new([0.0, 100.0, 0.0], 100.0),
+        ));
+        assert!(wl.point_inside_walls([0.0, 1.0, 1.0]));
+        wl.apply_walls(&mut c0, [0.0, 0.0, 0.0]);
+        assert_eq!(c0.volume(), 2.0);
+    }
 }
@@ -0,0 +1,208 @@
+// Wall shapes that are not part of voro++. They follow the same
+// conventions as the walls in voro++'s wall.cc: a curved wall cuts a cell
+// with a single plane tangent to the wall. The ellipsoid uses the tangent
+// plane where the ray from its center through the particle meets the
+// surface, which is not the nearest point unless the axes are equal. The
+// torus uses the tangent plane at the point of the tube nearest to the
+// particle. On the outer side of the tube the surface curves away from
+// this plane as a sphere does, but on the inner (saddle) side it does
+// not, so there the cut only approximates the wall.
+
+#include <cmath>
+#include <vector>
+#include "rust/cxx.h"
+
+namespace voro
+{
+    // A class representing an ellipsoidal wall object, whose axes are
+    // aligned with the coordinate axes.
+    class wall_ellipsoid : public wall
+    {
+    public:
+        wall_ellipsoid(double xc_, double yc_, double zc_, double xr_,
+                       double yr_, double zr_, int w_id_ = -99)
+            : w_id(w_id_), xc(xc_), yc(yc_), zc(zc_), xr(xr_), yr(yr_),
+              zr(zr_) {}
+        bool point_inside(double x, double y, double z)
+        {
+            double xs = (x - xc) / xr, ys = (y - yc) / yr,
+                   zs = (z - zc) / zr;
+            return xs * xs + ys * ys + zs * zs < 1;
+        }
+        template <class v_cell>
+        bool cut_cell_base(v_cell &c, double x, double y, double z)
+        {
+            double xs = (x - xc) / xr, ys = (y - yc) / yr,
+                   zs = (z - zc) / zr, dq = xs * xs + ys * ys + zs * zs;
+            if (dq > 1e-5)
+            {
+                // The ray from the center through the cell meets the
+                // surface at a fraction k of the way, and the gradient
+                // there is the normal of the tangent plane.
+                double k = 1 / sqrt(dq);
+                return c.nplane(xs / xr, ys / yr, zs / zr,
+                                2 * (k - 1) * dq, w_id);
+            }
+            return true;
+        }
+        bool cut_cell(voronoicell &c, double x, double y, double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+        bool cut_cell(voronoicell_neighbor &c, double x, double y,
+                      double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+
+    private:
+        const int w_id;
+        const double xc, yc, zc, xr, yr, zr;
+    };
+
+    // A class representing a toroidal wall object.
+    class wall_torus : public wall
+    {
+    public:
+        wall_torus(double xc_, double yc_, double zc_, double xa_,
+                   double ya_, double za_, double rmaj_, double rmin_,
+                   int w_id_ = -99)
+            : w_id(w_id_), xc(xc_), yc(yc_), zc(zc_), xa(xa_), ya(ya_),
+              za(za_), asq(xa_ * xa_ + ya_ * ya_ + za_ * za_),
+              rmaj(rmaj_), rmin(rmin_) {}
+        bool point_inside(double x, double y, double z)
+        {
+            double xd = x - xc, yd = y - yc, zd = z - zc,
+                   pa = (xd * xa + yd * ya + zd * za) / asq;
+            xd -= pa * xa;
+            yd -= pa * ya;
+            zd -= pa * za;
+            double s = sqrt(xd * xd + yd * yd + zd * zd) - rmaj;
+            return s * s + pa * pa * asq < rmin * rmin;
+        }
+        template <class v_cell>
+        bool cut_cell_base(v_cell &c, double x, double y, double z)
+        {
+            double xd = x - xc, yd = y - yc, zd = z - zc,
+                   pa = (xd * xa + yd * ya + zd * za) / asq;
+            double xr = xd - pa * xa, yr = yd - pa * ya, zr = zd - pa * za,
+                   rq = xr * xr + yr * yr + zr * zr;
+            if (rq > 1e-10)
+            {
+                // Treat the tube as a sphere centered at the nearest
+                // point of the central circle.
+                double k = rmaj / sqrt(rq);
+                xd -= k * xr;
+                yd -= k * yr;
+                zd -= k * zr;
+                double dq = xd * xd + yd * yd + zd * zd;
+                if (dq > 1e-5)
+                {
+                    dq = 2 * (sqrt(dq) * rmin - dq);
+                    return c.nplane(xd, yd, zd, dq, w_id);
+                }
+            }
+            return true;
+        }
+        bool cut_cell(voronoicell &c, double x, double y, double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+        bool cut_cell(voronoicell_neighbor &c, double x, double y,
+                      double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+
+    private:
+        const int w_id;
+        const double xc, yc, zc, xa, ya, za, asq, rmaj, rmin;
+    };
+
+    // A class representing a box wall object, whose faces are aligned
+    // with the coordinate axes. The box is convex, so the cut is exact.
+    class wall_box : public wall
+    {
+    public:
+        wall_box(double xmin_, double xmax_, double ymin_, double ymax_,
+                 double zmin_, double zmax_, int w_id_ = -99)
+            : w_id(w_id_), xmin(xmin_), xmax(xmax_), ymin(ymin_),
+              ymax(ymax_), zmin(zmin_), zmax(zmax_) {}
+        bool point_inside(double x, double y, double z)
+        {
+            return x > xmin && x < xmax && y > ymin && y < ymax &&
+                   z > zmin && z < zmax;
+        }
+        template <class v_cell>
+        bool cut_cell_base(v_cell &c, double x, double y, double z)
+        {
+            return c.nplane(1, 0, 0, 2 * (xmax - x), w_id) &&
+                   c.nplane(-1, 0, 0, 2 * (x - xmin), w_id) &&
+                   c.nplane(0, 1, 0, 2 * (ymax - y), w_id) &&
+                   c.nplane(0, -1, 0, 2 * (y - ymin), w_id) &&
+                   c.nplane(0, 0, 1, 2 * (zmax - z), w_id) &&
+                   c.nplane(0, 0, -1, 2 * (z - zmin), w_id);
+        }
+        bool cut_cell(voronoicell &c, double x, double y, double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+        bool cut_cell(voronoicell_neighbor &c, double x, double y,
+                      double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+
+    private:
+        const int w_id;
+        const double xmin, xmax, ymin, ymax, zmin, zmax;
+    };
+
+    // A class representing a convex polyhedral wall object, given as the
+    // intersection of the half-spaces n.x < a. Each face carries its own
+    // wall ID.
+    class wall_polyhedron : public wall
+    {
+    public:
+        wall_polyhedron(rust::Slice<const double> planes_,
+                        rust::Slice<const int> w_ids_)
+            : planes(planes_.begin(), planes_.end()),
+              w_ids(w_ids_.begin(), w_ids_.end()) {}
+        bool point_inside(double x, double y, double z)
+        {
+            for (size_t i = 0; i < w_ids.size(); i++)
+            {
+                const double *p = &planes[4 * i];
+                if (x * p[0] + y * p[1] + z * p[2] >= p[3])
+                    return false;
+            }
+            return true;
+        }
+        template <class v_cell>
+        bool cut_cell_base(v_cell &c, double x, double y, double z)
+        {
+            for (size_t i = 0; i < w_ids.size(); i++)
+            {
+                const double *p = &planes[4 * i];
+                double dotp = x * p[0] + y * p[1] + z * p[2];
+                if (!c.nplane(p[0], p[1], p[2], 2 * (p[3] - dotp),
+                              w_ids[i]))
+                    return false;
+            }
+            return true;
+        }
+        bool cut_cell(voronoicell &c, double x, double y, double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+        bool cut_cell(voronoicell_neighbor &c, double x, double y,
+                      double z)
+        {
+            return cut_cell_base(c, x, y, z);
+        }
+
+    private:
+        std::vector<double> planes;
+        std::vector<int> w_ids;
+    };
+}